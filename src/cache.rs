@@ -0,0 +1,218 @@
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+#[cfg(feature = "async")]
+use crate::driver::SGM41511Async;
+use crate::driver::SGM41511;
+use crate::registers::{Readable, Writable};
+use crate::types::*;
+use crate::{Error, Register};
+
+/// The cached configuration registers, indexed by address.
+const CACHED: [Register; 8] = [
+    Register::Reg00,
+    Register::Reg01,
+    Register::Reg02,
+    Register::Reg03,
+    Register::Reg04,
+    Register::Reg05,
+    Register::Reg06,
+    Register::Reg07,
+];
+
+const CACHED_COUNT: usize = CACHED.len();
+
+/// I2C Watchdog Timer Reset bit of REG01. Self-clearing, never cached.
+const WD_RST: u8 = 0x40;
+
+/// Driver wrapper keeping a shadow copy of the configuration registers
+/// REG00 - REG07.
+///
+/// Reads of a cached register only touch the bus the first time, and
+/// modifications are collected in the shadow copy until [`flush`] writes
+/// the dirty registers. REG08 - REG0B and the self-clearing WD_RST bit
+/// always go straight to the chip.
+///
+/// Call [`invalidate`] whenever the chip may have reset its registers.
+///
+/// [`flush`]: CachedSGM41511::flush
+/// [`invalidate`]: CachedSGM41511::invalidate
+#[maybe_async_cfg::maybe(
+    idents(SGM41511(sync = "SGM41511", async = "SGM41511Async")),
+    sync(self = "CachedSGM41511"),
+    async(feature = "async", self = "CachedSGM41511Async")
+)]
+pub struct CachedSGM41511<I2C> {
+    device: SGM41511<I2C>,
+    shadow: [u8; CACHED_COUNT],
+    valid: u8,
+    dirty: u8,
+}
+
+#[maybe_async_cfg::maybe(
+    idents(SGM41511(sync = "SGM41511", async = "SGM41511Async")),
+    sync(self = "CachedSGM41511"),
+    async(feature = "async", self = "CachedSGM41511Async")
+)]
+impl<I2C> CachedSGM41511<I2C> {
+    pub fn new(device: SGM41511<I2C>) -> Self {
+        Self {
+            device,
+            shadow: [0; CACHED_COUNT],
+            valid: 0,
+            dirty: 0,
+        }
+    }
+
+    /// Returns the wrapped driver. Unflushed modifications are lost.
+    pub fn into_inner(self) -> SGM41511<I2C> {
+        self.device
+    }
+
+    /// Direct access to the wrapped driver, bypassing the cache.
+    ///
+    /// Writes made through it are not seen by the cache; call
+    /// [`invalidate`](CachedSGM41511::invalidate) afterwards.
+    pub fn device_mut(&mut self) -> &mut SGM41511<I2C> {
+        &mut self.device
+    }
+
+    /// Drops the whole shadow copy, including unflushed modifications.
+    pub fn invalidate(&mut self) {
+        self.valid = 0;
+        self.dirty = 0;
+    }
+
+    /// Returns `true` if `register` has modifications that were not flushed.
+    pub fn is_dirty(&self, register: Register) -> bool {
+        Self::cache_bit(register).is_some_and(|bit| self.dirty & bit != 0)
+    }
+
+    fn cache_bit(register: Register) -> Option<u8> {
+        let index = register as usize;
+        (index < CACHED_COUNT).then(|| 1 << index)
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        I2c(sync = "BlockingI2c", async = "AsyncI2c"),
+        SGM41511(sync = "SGM41511", async = "SGM41511Async")
+    ),
+    sync(self = "CachedSGM41511"),
+    async(feature = "async", self = "CachedSGM41511Async")
+)]
+impl<I2C, E> CachedSGM41511<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Fills the shadow copy of REG00 - REG07 with a single burst read.
+    /// Registers with unflushed modifications keep their shadow value.
+    pub async fn load(&mut self) -> Result<(), Error<E>> {
+        let mut data = [0u8; CACHED_COUNT];
+        self.device
+            .read_registers(Register::Reg00, &mut data)
+            .await?;
+        for (index, value) in data.iter().enumerate() {
+            if self.dirty & (1 << index) == 0 {
+                self.shadow[index] = Self::strip_volatile(index, *value);
+            }
+        }
+        self.valid = 0xff;
+        Ok(())
+    }
+
+    /// Returns the raw value of `register`, from the shadow copy if it is
+    /// cached and valid.
+    pub async fn read_raw(&mut self, register: Register) -> Result<u8, Error<E>> {
+        let bit = match Self::cache_bit(register) {
+            Some(bit) => bit,
+            None => return self.device.read_register(register).await,
+        };
+        let index = register as usize;
+        if self.valid & bit == 0 {
+            let data = self.device.read_register(register).await?;
+            self.shadow[index] = Self::strip_volatile(index, data);
+            self.valid |= bit;
+        }
+        Ok(self.shadow[index])
+    }
+
+    /// Decodes the register `R`, from the shadow copy if it is cached and
+    /// valid.
+    pub async fn read<R: Readable>(&mut self) -> Result<R::Value, Error<E>> {
+        let data = self.read_raw(R::ADDRESS).await?;
+        R::Value::try_from(data).map_err(|_| Error::InvalidFieldValue {
+            register: R::ADDRESS,
+            raw: data,
+        })
+    }
+
+    /// Applies `f` to the cached value of the register `R` and marks the
+    /// register dirty if it changed. Nothing is written until [`flush`].
+    ///
+    /// Registers outside REG00 - REG07 are modified on the chip right away.
+    /// Setting `watchdog_reset` in REG01 kicks the watchdog immediately.
+    ///
+    /// [`flush`]: CachedSGM41511::flush
+    pub async fn modify<R, F>(&mut self, f: F) -> Result<Modified<R::Value>, Error<E>>
+    where
+        R: Readable + Writable,
+        F: FnOnce(&mut R::Value),
+    {
+        let register = R::ADDRESS;
+        let bit = match Self::cache_bit(register) {
+            Some(bit) => bit,
+            None => return self.device.modify::<R, F>(f).await,
+        };
+        let index = register as usize;
+        let old = self.read::<R>().await?;
+        let mut new = old;
+        f(&mut new);
+        let value: u8 = new.into();
+        if register == Register::Reg01 && value & WD_RST != 0 {
+            self.device.write_register(register, value).await?;
+            self.shadow[index] = value & !WD_RST;
+            self.dirty &= !bit;
+        } else if new != old {
+            self.shadow[index] = value;
+            self.dirty |= bit;
+        }
+        Ok(Modified { old, new })
+    }
+
+    /// Writes every dirty register to the chip, in address order.
+    pub async fn flush(&mut self) -> Result<(), Error<E>> {
+        for (index, register) in CACHED.iter().enumerate() {
+            let bit = 1 << index;
+            if self.dirty & bit == 0 {
+                continue;
+            }
+            self.device
+                .write_register(*register, self.shadow[index])
+                .await?;
+            self.dirty &= !bit;
+        }
+        Ok(())
+    }
+
+    /// Sets WD_RST in REG01, keeping the other bits from the shadow copy.
+    pub async fn kick_watchdog(&mut self) -> Result<(), Error<E>> {
+        let data = self.read_raw(Register::Reg01).await?;
+        self.device
+            .write_register(Register::Reg01, data | WD_RST)
+            .await?;
+        self.dirty &= !(1 << Register::Reg01 as usize);
+        Ok(())
+    }
+
+    fn strip_volatile(index: usize, value: u8) -> u8 {
+        if index == Register::Reg01 as usize {
+            value & !WD_RST
+        } else {
+            value
+        }
+    }
+}
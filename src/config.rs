@@ -0,0 +1,766 @@
+//! High-level charger configuration.
+//!
+//! [`ChargerConfig`] gathers every writable setting of REG00 - REG07 and the
+//! interrupt mask of REG0A. [`apply`](ChargerConfig::apply) brings the chip
+//! to the configuration, writing only the registers that differ, and
+//! [`verify`](ChargerConfig::verify) reads it back.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sgm41511::config::ChargerConfig;
+//! use sgm41511::types::*;
+//! let config = ChargerConfig {
+//!     charge_current: ChargeCurrent::_1020mA,
+//!     charge_voltage_limit: ChargeVoltageLimit::_4_352V,
+//!     watchdog_timer_setting: WatchDogTimerSetting::Disabled,
+//!     ..ChargerConfig::default()
+//! };
+//! let mismatches = config.diff(&ChargerConfig::default());
+//! assert_eq!(mismatches.len(), 3);
+//! ```
+
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+#[cfg(feature = "async")]
+use crate::driver::SGM41511Async;
+use crate::driver::SGM41511;
+use crate::register_map::RegisterMap;
+use crate::registers::Readable;
+use crate::types::*;
+use crate::{Error, Register};
+
+/// Every writable setting of the charger.
+///
+/// The fields are named after the matching `RegXXValues` fields. The
+/// self-clearing WD_RST, IINDET_EN and REG_RST bits are not part of the
+/// configuration. [`Default`] gives the power-on reset configuration.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChargerConfig {
+    // REG00
+    pub en_hiz: bool,
+    pub en_ichg_mon: bool,
+    pub input_milliamps_limit: InputCurrentLimit,
+    // REG01
+    pub pfm_disabled: bool,
+    pub otg_enabled: bool,
+    pub charge_enabled: bool,
+    pub sys_min_voltage: MinSystemVoltage,
+    pub min_bat_sel: MinBatteryVoltageForOtG,
+    // REG02
+    pub boost_current_limit: BoostCurrentLimit,
+    pub q1_full_on: Q1FullOnMode,
+    pub charge_current: ChargeCurrent,
+    // REG03
+    pub pre_charge_current: PreChargeCurrent,
+    pub term_charge_current: TermChargeCurrent,
+    // REG04
+    pub charge_voltage_limit: ChargeVoltageLimit,
+    pub top_off_timer: TopOffTimer,
+    pub battery_recharge_threshold: BatteryRechargeThreshold,
+    // REG05
+    pub term_enabled: bool,
+    pub watchdog_timer_setting: WatchDogTimerSetting,
+    pub timer_enabled: bool,
+    pub charge_timer_setting: ChargeTimerSetting,
+    pub thermal_regulation_threshold: ThermalRegulationThreshold,
+    pub jeita_charging_current: JEITAChargingCurrent,
+    // REG06
+    pub ovp_threshold: OVPThreshold,
+    pub boost_mode_voltage: BoostModeVoltage,
+    pub vindpm_threshold: VINDPMThreshold,
+    // REG07
+    pub tmr2x_enabled: bool,
+    pub batfet_disabled: bool,
+    pub jeita_voltage_setting: JEITAVoltageSetting,
+    pub batfet_delay: bool,
+    pub batfet_reset_enabled: bool,
+    pub vdpm_battery_tracking: VDPMBatteryVoltageTracking,
+    // REG0A
+    pub interrupt_mask: InterruptMask,
+}
+
+impl Default for ChargerConfig {
+    fn default() -> Self {
+        ChargerConfig::RESET
+    }
+}
+
+/// A single setting of [`ChargerConfig`].
+#[repr(u8)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConfigField {
+    /// HIZ mode
+    EnHiz,
+    /// STAT pin output
+    EnIchgMon,
+    /// Input current limit (IINDPM)
+    InputCurrentLimit,
+    /// PFM disabled
+    PfmDisabled,
+    /// OTG (boost) mode
+    OtgEnabled,
+    /// Charging
+    ChargeEnabled,
+    /// Minimum system voltage (SYS_MIN)
+    SysMinVoltage,
+    /// Minimum battery voltage for OTG
+    MinBatSel,
+    /// Boost mode current limit
+    BoostCurrentLimit,
+    /// Q1 full on mode
+    Q1FullOn,
+    /// Fast charge current (ICHG)
+    ChargeCurrent,
+    /// Pre-charge current (IPRECHG)
+    PreChargeCurrent,
+    /// Termination current (ITERM)
+    TermChargeCurrent,
+    /// Charge voltage limit (VREG)
+    ChargeVoltageLimit,
+    /// Top-off timer
+    TopOffTimer,
+    /// Recharge threshold below VREG
+    BatteryRechargeThreshold,
+    /// Charge termination
+    TermEnabled,
+    /// I2C watchdog timer
+    WatchdogTimerSetting,
+    /// Safety timer
+    TimerEnabled,
+    /// Fast charge safety timer
+    ChargeTimerSetting,
+    /// Thermal regulation threshold
+    ThermalRegulationThreshold,
+    /// JEITA cool charge current
+    JeitaChargingCurrent,
+    /// VAC over-voltage threshold
+    OvpThreshold,
+    /// Boost mode voltage
+    BoostModeVoltage,
+    /// Input voltage limit (VINDPM)
+    VindpmThreshold,
+    /// Half clock rate safety timer
+    Tmr2xEnabled,
+    /// BATFET disabled
+    BatfetDisabled,
+    /// JEITA warm charge voltage
+    JeitaVoltageSetting,
+    /// BATFET turn off delay
+    BatfetDelay,
+    /// BATFET reset
+    BatfetResetEnabled,
+    /// Dynamic VINDPM tracking
+    VdpmBatteryTracking,
+    /// VINDPM/IINDPM interrupt mask
+    InterruptMask,
+}
+
+impl ConfigField {
+    /// Every field, in register order.
+    pub const ALL: [ConfigField; 32] = [
+        ConfigField::EnHiz,
+        ConfigField::EnIchgMon,
+        ConfigField::InputCurrentLimit,
+        ConfigField::PfmDisabled,
+        ConfigField::OtgEnabled,
+        ConfigField::ChargeEnabled,
+        ConfigField::SysMinVoltage,
+        ConfigField::MinBatSel,
+        ConfigField::BoostCurrentLimit,
+        ConfigField::Q1FullOn,
+        ConfigField::ChargeCurrent,
+        ConfigField::PreChargeCurrent,
+        ConfigField::TermChargeCurrent,
+        ConfigField::ChargeVoltageLimit,
+        ConfigField::TopOffTimer,
+        ConfigField::BatteryRechargeThreshold,
+        ConfigField::TermEnabled,
+        ConfigField::WatchdogTimerSetting,
+        ConfigField::TimerEnabled,
+        ConfigField::ChargeTimerSetting,
+        ConfigField::ThermalRegulationThreshold,
+        ConfigField::JeitaChargingCurrent,
+        ConfigField::OvpThreshold,
+        ConfigField::BoostModeVoltage,
+        ConfigField::VindpmThreshold,
+        ConfigField::Tmr2xEnabled,
+        ConfigField::BatfetDisabled,
+        ConfigField::JeitaVoltageSetting,
+        ConfigField::BatfetDelay,
+        ConfigField::BatfetResetEnabled,
+        ConfigField::VdpmBatteryTracking,
+        ConfigField::InterruptMask,
+    ];
+
+    /// The register holding the field.
+    pub const fn register(self) -> Register {
+        match self {
+            ConfigField::EnHiz | ConfigField::EnIchgMon | ConfigField::InputCurrentLimit => {
+                Register::Reg00
+            }
+            ConfigField::PfmDisabled
+            | ConfigField::OtgEnabled
+            | ConfigField::ChargeEnabled
+            | ConfigField::SysMinVoltage
+            | ConfigField::MinBatSel => Register::Reg01,
+            ConfigField::BoostCurrentLimit | ConfigField::Q1FullOn | ConfigField::ChargeCurrent => {
+                Register::Reg02
+            }
+            ConfigField::PreChargeCurrent | ConfigField::TermChargeCurrent => Register::Reg03,
+            ConfigField::ChargeVoltageLimit
+            | ConfigField::TopOffTimer
+            | ConfigField::BatteryRechargeThreshold => Register::Reg04,
+            ConfigField::TermEnabled
+            | ConfigField::WatchdogTimerSetting
+            | ConfigField::TimerEnabled
+            | ConfigField::ChargeTimerSetting
+            | ConfigField::ThermalRegulationThreshold
+            | ConfigField::JeitaChargingCurrent => Register::Reg05,
+            ConfigField::OvpThreshold
+            | ConfigField::BoostModeVoltage
+            | ConfigField::VindpmThreshold => Register::Reg06,
+            ConfigField::Tmr2xEnabled
+            | ConfigField::BatfetDisabled
+            | ConfigField::JeitaVoltageSetting
+            | ConfigField::BatfetDelay
+            | ConfigField::BatfetResetEnabled
+            | ConfigField::VdpmBatteryTracking => Register::Reg07,
+            ConfigField::InterruptMask => Register::Reg0a,
+        }
+    }
+
+    /// The bits of the field within its register.
+    pub const fn mask(self) -> u8 {
+        match self {
+            ConfigField::EnHiz => 0x80,
+            ConfigField::EnIchgMon => 0x60,
+            ConfigField::InputCurrentLimit => 0x1f,
+            ConfigField::PfmDisabled => 0x80,
+            ConfigField::OtgEnabled => 0x20,
+            ConfigField::ChargeEnabled => 0x10,
+            ConfigField::SysMinVoltage => 0x0e,
+            ConfigField::MinBatSel => 0x01,
+            ConfigField::BoostCurrentLimit => 0x80,
+            ConfigField::Q1FullOn => 0x40,
+            ConfigField::ChargeCurrent => 0x3f,
+            ConfigField::PreChargeCurrent => 0xf0,
+            ConfigField::TermChargeCurrent => 0x0f,
+            ConfigField::ChargeVoltageLimit => 0xf8,
+            ConfigField::TopOffTimer => 0x06,
+            ConfigField::BatteryRechargeThreshold => 0x01,
+            ConfigField::TermEnabled => 0x80,
+            ConfigField::WatchdogTimerSetting => 0x30,
+            ConfigField::TimerEnabled => 0x08,
+            ConfigField::ChargeTimerSetting => 0x04,
+            ConfigField::ThermalRegulationThreshold => 0x02,
+            ConfigField::JeitaChargingCurrent => 0x01,
+            ConfigField::OvpThreshold => 0xc0,
+            ConfigField::BoostModeVoltage => 0x30,
+            ConfigField::VindpmThreshold => 0x0f,
+            ConfigField::Tmr2xEnabled => 0x40,
+            ConfigField::BatfetDisabled => 0x20,
+            ConfigField::JeitaVoltageSetting => 0x10,
+            ConfigField::BatfetDelay => 0x08,
+            ConfigField::BatfetResetEnabled => 0x04,
+            ConfigField::VdpmBatteryTracking => 0x03,
+            ConfigField::InterruptMask => 0x03,
+        }
+    }
+}
+
+/// The set of fields that differ between two configurations.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Mismatches(u32);
+
+impl Mismatches {
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Number of mismatching fields.
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn contains(&self, field: ConfigField) -> bool {
+        self.0 & (1 << field as u8) != 0
+    }
+
+    fn insert(&mut self, field: ConfigField) {
+        self.0 |= 1 << field as u8;
+    }
+
+    /// Iterates over the mismatching fields, in register order.
+    pub fn iter(&self) -> impl Iterator<Item = ConfigField> + '_ {
+        ConfigField::ALL
+            .into_iter()
+            .filter(|field| self.contains(*field))
+    }
+}
+
+/// The configuration read back by [`ChargerConfig::verify`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Readback {
+    /// The configuration the chip holds. Field codes not defined in the
+    /// datasheet are decoded leniently.
+    pub actual: ChargerConfig,
+    /// The fields that differ from the expected configuration, or hold an
+    /// undefined code.
+    pub mismatches: Mismatches,
+}
+
+impl ChargerConfig {
+    /// Power-on reset configuration, built from the `RegXXValues::RESET`
+    /// constants.
+    pub const RESET: ChargerConfig = ChargerConfig {
+        en_hiz: Reg00Values::RESET.en_hiz,
+        en_ichg_mon: Reg00Values::RESET.en_ichg_mon,
+        input_milliamps_limit: Reg00Values::RESET.input_milliamps_limit,
+        pfm_disabled: Reg01Values::RESET.pfm_disabled,
+        otg_enabled: Reg01Values::RESET.otg_enabled,
+        charge_enabled: Reg01Values::RESET.charge_enabled,
+        sys_min_voltage: Reg01Values::RESET.sys_min_voltage,
+        min_bat_sel: Reg01Values::RESET.min_bat_sel,
+        boost_current_limit: Reg02Values::RESET.boost_current_limit,
+        q1_full_on: Reg02Values::RESET.q1_full_on,
+        charge_current: Reg02Values::RESET.charge_current,
+        pre_charge_current: Reg03Values::RESET.pre_charge_current,
+        term_charge_current: Reg03Values::RESET.term_charge_current,
+        charge_voltage_limit: Reg04Values::RESET.charge_voltage_limit,
+        top_off_timer: Reg04Values::RESET.top_off_timer,
+        battery_recharge_threshold: Reg04Values::RESET.battery_recharge_threshold,
+        term_enabled: Reg05Values::RESET.term_enabled,
+        watchdog_timer_setting: Reg05Values::RESET.watchdog_timer_setting,
+        timer_enabled: Reg05Values::RESET.timer_enabled,
+        charge_timer_setting: Reg05Values::RESET.charge_timer_setting,
+        thermal_regulation_threshold: Reg05Values::RESET.thermal_regulation_threshold,
+        jeita_charging_current: Reg05Values::RESET.jeita_charging_current,
+        ovp_threshold: Reg06Values::RESET.ovp_threshold,
+        boost_mode_voltage: Reg06Values::RESET.boost_mode_voltage,
+        vindpm_threshold: Reg06Values::RESET.vindpm_threshold,
+        tmr2x_enabled: Reg07Values::RESET.tmr2x_enabled,
+        batfet_disabled: Reg07Values::RESET.batfet_disabled,
+        jeita_voltage_setting: Reg07Values::RESET.jeita_voltage_setting,
+        batfet_delay: Reg07Values::RESET.batfet_delay,
+        batfet_reset_enabled: Reg07Values::RESET.batfet_reset_enabled,
+        vdpm_battery_tracking: Reg07Values::RESET.vdpm_battery_tracking,
+        interrupt_mask: InterruptMask::NONE,
+    };
+
+    /// Builds the configuration from a register snapshot. Fails if one of
+    /// the configuration registers holds an undefined field value.
+    pub fn from_register_map(map: &RegisterMap) -> Result<Self, InvalidValue> {
+        Self::decode_map(map).map_err(|register| InvalidValue(map.raw(register)))
+    }
+
+    /// Returns the fields where `other` differs from `self`.
+    pub fn diff(&self, other: &ChargerConfig) -> Mismatches {
+        let mut mismatches = Mismatches::default();
+        if self.en_hiz != other.en_hiz {
+            mismatches.insert(ConfigField::EnHiz);
+        }
+        if self.en_ichg_mon != other.en_ichg_mon {
+            mismatches.insert(ConfigField::EnIchgMon);
+        }
+        if self.input_milliamps_limit != other.input_milliamps_limit {
+            mismatches.insert(ConfigField::InputCurrentLimit);
+        }
+        if self.pfm_disabled != other.pfm_disabled {
+            mismatches.insert(ConfigField::PfmDisabled);
+        }
+        if self.otg_enabled != other.otg_enabled {
+            mismatches.insert(ConfigField::OtgEnabled);
+        }
+        if self.charge_enabled != other.charge_enabled {
+            mismatches.insert(ConfigField::ChargeEnabled);
+        }
+        if self.sys_min_voltage != other.sys_min_voltage {
+            mismatches.insert(ConfigField::SysMinVoltage);
+        }
+        if self.min_bat_sel != other.min_bat_sel {
+            mismatches.insert(ConfigField::MinBatSel);
+        }
+        if self.boost_current_limit != other.boost_current_limit {
+            mismatches.insert(ConfigField::BoostCurrentLimit);
+        }
+        if self.q1_full_on != other.q1_full_on {
+            mismatches.insert(ConfigField::Q1FullOn);
+        }
+        if self.charge_current != other.charge_current {
+            mismatches.insert(ConfigField::ChargeCurrent);
+        }
+        if self.pre_charge_current != other.pre_charge_current {
+            mismatches.insert(ConfigField::PreChargeCurrent);
+        }
+        if self.term_charge_current != other.term_charge_current {
+            mismatches.insert(ConfigField::TermChargeCurrent);
+        }
+        if self.charge_voltage_limit != other.charge_voltage_limit {
+            mismatches.insert(ConfigField::ChargeVoltageLimit);
+        }
+        if self.top_off_timer != other.top_off_timer {
+            mismatches.insert(ConfigField::TopOffTimer);
+        }
+        if self.battery_recharge_threshold != other.battery_recharge_threshold {
+            mismatches.insert(ConfigField::BatteryRechargeThreshold);
+        }
+        if self.term_enabled != other.term_enabled {
+            mismatches.insert(ConfigField::TermEnabled);
+        }
+        if self.watchdog_timer_setting != other.watchdog_timer_setting {
+            mismatches.insert(ConfigField::WatchdogTimerSetting);
+        }
+        if self.timer_enabled != other.timer_enabled {
+            mismatches.insert(ConfigField::TimerEnabled);
+        }
+        if self.charge_timer_setting != other.charge_timer_setting {
+            mismatches.insert(ConfigField::ChargeTimerSetting);
+        }
+        if self.thermal_regulation_threshold != other.thermal_regulation_threshold {
+            mismatches.insert(ConfigField::ThermalRegulationThreshold);
+        }
+        if self.jeita_charging_current != other.jeita_charging_current {
+            mismatches.insert(ConfigField::JeitaChargingCurrent);
+        }
+        if self.ovp_threshold != other.ovp_threshold {
+            mismatches.insert(ConfigField::OvpThreshold);
+        }
+        if self.boost_mode_voltage != other.boost_mode_voltage {
+            mismatches.insert(ConfigField::BoostModeVoltage);
+        }
+        if self.vindpm_threshold != other.vindpm_threshold {
+            mismatches.insert(ConfigField::VindpmThreshold);
+        }
+        if self.tmr2x_enabled != other.tmr2x_enabled {
+            mismatches.insert(ConfigField::Tmr2xEnabled);
+        }
+        if self.batfet_disabled != other.batfet_disabled {
+            mismatches.insert(ConfigField::BatfetDisabled);
+        }
+        if self.jeita_voltage_setting != other.jeita_voltage_setting {
+            mismatches.insert(ConfigField::JeitaVoltageSetting);
+        }
+        if self.batfet_delay != other.batfet_delay {
+            mismatches.insert(ConfigField::BatfetDelay);
+        }
+        if self.batfet_reset_enabled != other.batfet_reset_enabled {
+            mismatches.insert(ConfigField::BatfetResetEnabled);
+        }
+        if self.vdpm_battery_tracking != other.vdpm_battery_tracking {
+            mismatches.insert(ConfigField::VdpmBatteryTracking);
+        }
+        if self.interrupt_mask != other.interrupt_mask {
+            mismatches.insert(ConfigField::InterruptMask);
+        }
+        mismatches
+    }
+
+    /// Returns the fields that deviate from the power-on reset configuration.
+    pub fn diff_from_reset(&self) -> Mismatches {
+        self.diff(&ChargerConfig::RESET)
+    }
+
+    fn fill_reg00(&self, values: &mut Reg00Values) {
+        values.en_hiz = self.en_hiz;
+        values.en_ichg_mon = self.en_ichg_mon;
+        values.input_milliamps_limit = self.input_milliamps_limit;
+    }
+
+    fn fill_reg01(&self, values: &mut Reg01Values) {
+        values.pfm_disabled = self.pfm_disabled;
+        values.otg_enabled = self.otg_enabled;
+        values.charge_enabled = self.charge_enabled;
+        values.sys_min_voltage = self.sys_min_voltage;
+        values.min_bat_sel = self.min_bat_sel;
+        values.watchdog_reset = false;
+    }
+
+    fn fill_reg02(&self, values: &mut Reg02Values) {
+        values.boost_current_limit = self.boost_current_limit;
+        values.q1_full_on = self.q1_full_on;
+        values.charge_current = self.charge_current;
+    }
+
+    fn fill_reg03(&self, values: &mut Reg03Values) {
+        values.pre_charge_current = self.pre_charge_current;
+        values.term_charge_current = self.term_charge_current;
+    }
+
+    fn fill_reg04(&self, values: &mut Reg04Values) {
+        values.charge_voltage_limit = self.charge_voltage_limit;
+        values.top_off_timer = self.top_off_timer;
+        values.battery_recharge_threshold = self.battery_recharge_threshold;
+    }
+
+    fn fill_reg05(&self, values: &mut Reg05Values) {
+        values.term_enabled = self.term_enabled;
+        values.watchdog_timer_setting = self.watchdog_timer_setting;
+        values.timer_enabled = self.timer_enabled;
+        values.charge_timer_setting = self.charge_timer_setting;
+        values.thermal_regulation_threshold = self.thermal_regulation_threshold;
+        values.jeita_charging_current = self.jeita_charging_current;
+    }
+
+    fn fill_reg06(&self, values: &mut Reg06Values) {
+        values.ovp_threshold = self.ovp_threshold;
+        values.boost_mode_voltage = self.boost_mode_voltage;
+        values.vindpm_threshold = self.vindpm_threshold;
+    }
+
+    fn fill_reg07(&self, values: &mut Reg07Values) {
+        values.tmr2x_enabled = self.tmr2x_enabled;
+        values.batfet_disabled = self.batfet_disabled;
+        values.jeita_voltage_setting = self.jeita_voltage_setting;
+        values.batfet_delay = self.batfet_delay;
+        values.batfet_reset_enabled = self.batfet_reset_enabled;
+        values.vdpm_battery_tracking = self.vdpm_battery_tracking;
+    }
+
+    fn fill_reg0a(&self, values: &mut Reg0aValues) {
+        values.set_interrupt_mask(self.interrupt_mask);
+    }
+
+    /// Decodes the configuration registers of `map`, returning the first
+    /// register holding an undefined field value on failure.
+    pub(crate) fn decode_map(map: &RegisterMap) -> Result<Self, Register> {
+        Ok(Self::from_values(
+            decode::<Reg00Values>(map)?,
+            decode::<Reg01Values>(map)?,
+            decode::<Reg02Values>(map)?,
+            decode::<Reg03Values>(map)?,
+            decode::<Reg04Values>(map)?,
+            decode::<Reg05Values>(map)?,
+            decode::<Reg06Values>(map)?,
+            decode::<Reg07Values>(map)?,
+            decode::<Reg0aValues>(map)?,
+        ))
+    }
+
+    /// Decodes the configuration registers of `map` leniently. Also returns
+    /// the fields that held an undefined code and were clamped.
+    pub(crate) fn decode_map_lenient(map: &RegisterMap) -> (Self, Mismatches) {
+        let mut clamped = Mismatches::default();
+        let mut note_clamped = |register: Register, encoded: u8| {
+            let changed = map.raw(register) ^ encoded;
+            for field in ConfigField::ALL {
+                if field.register() == register && field.mask() & changed != 0 {
+                    clamped.insert(field);
+                }
+            }
+        };
+        let reg00 = map.lenient::<Reg00Values>(Register::Reg00).value;
+        note_clamped(Register::Reg00, reg00.into());
+        let reg01 = map.lenient::<Reg01Values>(Register::Reg01).value;
+        note_clamped(Register::Reg01, reg01.into());
+        let reg02 = map.lenient::<Reg02Values>(Register::Reg02).value;
+        note_clamped(Register::Reg02, reg02.into());
+        let reg03 = map.lenient::<Reg03Values>(Register::Reg03).value;
+        note_clamped(Register::Reg03, reg03.into());
+        let reg04 = map.lenient::<Reg04Values>(Register::Reg04).value;
+        note_clamped(Register::Reg04, reg04.into());
+        let reg05 = map.lenient::<Reg05Values>(Register::Reg05).value;
+        note_clamped(Register::Reg05, reg05.into());
+        let reg06 = map.lenient::<Reg06Values>(Register::Reg06).value;
+        note_clamped(Register::Reg06, reg06.into());
+        let reg07 = map.lenient::<Reg07Values>(Register::Reg07).value;
+        note_clamped(Register::Reg07, reg07.into());
+        let reg0a = map.lenient::<Reg0aValues>(Register::Reg0a).value;
+        note_clamped(Register::Reg0a, reg0a.into());
+        let config = Self::from_values(
+            reg00, reg01, reg02, reg03, reg04, reg05, reg06, reg07, reg0a,
+        );
+        (config, clamped)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn from_values(
+        reg00: Reg00Values,
+        reg01: Reg01Values,
+        reg02: Reg02Values,
+        reg03: Reg03Values,
+        reg04: Reg04Values,
+        reg05: Reg05Values,
+        reg06: Reg06Values,
+        reg07: Reg07Values,
+        reg0a: Reg0aValues,
+    ) -> Self {
+        ChargerConfig {
+            en_hiz: reg00.en_hiz,
+            en_ichg_mon: reg00.en_ichg_mon,
+            input_milliamps_limit: reg00.input_milliamps_limit,
+            pfm_disabled: reg01.pfm_disabled,
+            otg_enabled: reg01.otg_enabled,
+            charge_enabled: reg01.charge_enabled,
+            sys_min_voltage: reg01.sys_min_voltage,
+            min_bat_sel: reg01.min_bat_sel,
+            boost_current_limit: reg02.boost_current_limit,
+            q1_full_on: reg02.q1_full_on,
+            charge_current: reg02.charge_current,
+            pre_charge_current: reg03.pre_charge_current,
+            term_charge_current: reg03.term_charge_current,
+            charge_voltage_limit: reg04.charge_voltage_limit,
+            top_off_timer: reg04.top_off_timer,
+            battery_recharge_threshold: reg04.battery_recharge_threshold,
+            term_enabled: reg05.term_enabled,
+            watchdog_timer_setting: reg05.watchdog_timer_setting,
+            timer_enabled: reg05.timer_enabled,
+            charge_timer_setting: reg05.charge_timer_setting,
+            thermal_regulation_threshold: reg05.thermal_regulation_threshold,
+            jeita_charging_current: reg05.jeita_charging_current,
+            ovp_threshold: reg06.ovp_threshold,
+            boost_mode_voltage: reg06.boost_mode_voltage,
+            vindpm_threshold: reg06.vindpm_threshold,
+            tmr2x_enabled: reg07.tmr2x_enabled,
+            batfet_disabled: reg07.batfet_disabled,
+            jeita_voltage_setting: reg07.jeita_voltage_setting,
+            batfet_delay: reg07.batfet_delay,
+            batfet_reset_enabled: reg07.batfet_reset_enabled,
+            vdpm_battery_tracking: reg07.vdpm_battery_tracking,
+            interrupt_mask: reg0a.interrupt_mask(),
+        }
+    }
+
+    /// Returns the byte `register` must hold for this configuration. The
+    /// bits outside the configuration are taken from `raw`; undefined field
+    /// codes in `raw` are decoded leniently and get overwritten.
+    fn encode(&self, register: Register, raw: u8) -> u8 {
+        match register {
+            Register::Reg00 => self.encode_with::<Reg00Values>(raw, Self::fill_reg00),
+            Register::Reg01 => self.encode_with::<Reg01Values>(raw, Self::fill_reg01),
+            Register::Reg02 => self.encode_with::<Reg02Values>(raw, Self::fill_reg02),
+            Register::Reg03 => self.encode_with::<Reg03Values>(raw, Self::fill_reg03),
+            Register::Reg04 => self.encode_with::<Reg04Values>(raw, Self::fill_reg04),
+            Register::Reg05 => self.encode_with::<Reg05Values>(raw, Self::fill_reg05),
+            Register::Reg06 => self.encode_with::<Reg06Values>(raw, Self::fill_reg06),
+            Register::Reg07 => self.encode_with::<Reg07Values>(raw, Self::fill_reg07),
+            Register::Reg0a => self.encode_with::<Reg0aValues>(raw, Self::fill_reg0a),
+            Register::Reg08 | Register::Reg09 | Register::Reg0b => raw,
+        }
+    }
+
+    /// The value to write to `register`, holding `raw`, before the limits:
+    /// REG01 with the charge and OTG enables turned off that the
+    /// configuration turns off, and REG00 with HIZ mode entered if the
+    /// configuration enters it. `None` if there is nothing to do early.
+    fn early(&self, register: Register, raw: u8) -> Option<u8> {
+        let target = self.encode(register, raw);
+        match register {
+            Register::Reg01 => {
+                let enables = ConfigField::ChargeEnabled.mask() | ConfigField::OtgEnabled.mask();
+                let disabled = raw & !target & enables;
+                (disabled != 0).then_some(raw & !disabled)
+            }
+            Register::Reg00 => {
+                let hiz = ConfigField::EnHiz.mask();
+                (raw & hiz == 0 && target & hiz != 0).then_some(raw | hiz)
+            }
+            _ => None,
+        }
+    }
+
+    fn encode_with<T>(&self, raw: u8, fill: fn(&Self, &mut T)) -> u8
+    where
+        T: DecodeLenient + Into<u8>,
+    {
+        let mut values = T::decode_lenient(raw);
+        fill(self, &mut values);
+        values.into()
+    }
+}
+
+/// The registers written by [`ChargerConfig::apply`], in order, once the
+/// early writes of [`ChargerConfig::early`] are done.
+const APPLY_ORDER: [Register; 9] = [
+    Register::Reg05,
+    Register::Reg06,
+    Register::Reg00,
+    Register::Reg03,
+    Register::Reg02,
+    Register::Reg04,
+    Register::Reg07,
+    Register::Reg0a,
+    Register::Reg01,
+];
+
+fn decode<R: Readable>(map: &RegisterMap) -> Result<R::Value, Register> {
+    R::Value::try_from(map.raw(R::ADDRESS)).map_err(|_| R::ADDRESS)
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        I2c(sync = "BlockingI2c", async = "AsyncI2c"),
+        SGM41511(sync = "SGM41511", async = "SGM41511Async"),
+        apply(sync = "apply", async = "apply_async"),
+        apply_from(sync = "apply_from", async = "apply_from_async"),
+        verify(sync = "verify", async = "verify_async")
+    ),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl ChargerConfig {
+    /// Writes the configuration to the chip. The registers are read first
+    /// and only the ones that differ are written. Undefined field codes read
+    /// from the chip are overwritten like any other mismatch.
+    ///
+    /// Charging or OTG being turned off, and HIZ mode being entered, are
+    /// written before anything else, so the chip does not keep charging or
+    /// boosting while the limits change. Then the watchdog timer setting in
+    /// REG05 and the limits follow. REG01 goes last, so charging or OTG is
+    /// only (re)enabled once the new limits are in place.
+    pub async fn apply<I2C, E>(&self, device: &mut SGM41511<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let map = device.read_settings().await?;
+        Self::apply_from(self, device, &map).await
+    }
+
+    /// Like [`apply`](Self::apply), but takes the current register values
+    /// from `current` instead of reading them. `current` must be an
+    /// up-to-date snapshot of the chip.
+    pub async fn apply_from<I2C, E>(
+        &self,
+        device: &mut SGM41511<I2C>,
+        current: &RegisterMap,
+    ) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let mut raw = *current.as_raw();
+        for register in [Register::Reg01, Register::Reg00] {
+            if let Some(value) = self.early(register, raw[register as usize]) {
+                device.write_register(register, value).await?;
+                raw[register as usize] = value;
+            }
+        }
+        for register in APPLY_ORDER {
+            let value = self.encode(register, raw[register as usize]);
+            if value != raw[register as usize] {
+                device.write_register(register, value).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the configuration registers back and returns them along with
+    /// the fields that do not match.
+    ///
+    /// REG09 is not read, so the latched faults are left alone.
+    pub async fn verify<I2C, E>(&self, device: &mut SGM41511<I2C>) -> Result<Readback, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let map = device.read_settings().await?;
+        let (actual, clamped) = Self::decode_map_lenient(&map);
+        let mismatches = Mismatches(self.diff(&actual).0 | clamped.0);
+        Ok(Readback { actual, mismatches })
+    }
+}
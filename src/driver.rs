@@ -0,0 +1,510 @@
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::faults::FaultReport;
+use crate::register_map::*;
+use crate::registers::{Readable, Writable};
+use crate::status::ChargerStatus;
+use crate::types::*;
+use crate::units::Rounding;
+use crate::{Error, Register, SGM41511_ADDR};
+
+#[maybe_async_cfg::maybe(
+    sync(self = "SGM41511"),
+    async(feature = "async", self = "SGM41511Async")
+)]
+pub struct SGM41511<I2C> {
+    i2c: I2C,
+}
+
+#[maybe_async_cfg::maybe(
+    idents(I2c(sync = "BlockingI2c", async = "AsyncI2c")),
+    sync(self = "SGM41511"),
+    async(feature = "async", self = "SGM41511Async")
+)]
+impl<I2C, E> SGM41511<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    pub fn new(i2c: I2C) -> Self {
+        Self { i2c }
+    }
+
+    #[inline(always)]
+    pub async fn read_register(&mut self, register: Register) -> Result<u8, Error<E>> {
+        let mut data = [0u8; 1];
+        self.i2c
+            .write_read(SGM41511_ADDR, &[register as u8], &mut data)
+            .await?;
+        Ok(data[0])
+    }
+
+    #[inline(always)]
+    pub async fn write_register(&mut self, register: Register, value: u8) -> Result<(), Error<E>> {
+        self.i2c
+            .write(SGM41511_ADDR, &[register as u8, value])
+            .await?;
+        Ok(())
+    }
+
+    /// Reads `buf.len()` consecutive registers starting at `start` in a single
+    /// auto-incrementing transaction.
+    #[inline(always)]
+    pub async fn read_registers(
+        &mut self,
+        start: Register,
+        buf: &mut [u8],
+    ) -> Result<(), Error<E>> {
+        self.i2c
+            .write_read(SGM41511_ADDR, &[start as u8], buf)
+            .await?;
+        Ok(())
+    }
+
+    /// Reads REG00 - REG0B in a single transaction.
+    ///
+    /// Reading REG09 clears its latched faults, see
+    /// [`read_faults`](Self::read_faults).
+    #[inline(always)]
+    pub async fn read_all(&mut self) -> Result<RegisterMap, Error<E>> {
+        let mut data = [0u8; REGISTER_COUNT];
+        self.read_registers(Register::Reg00, &mut data).await?;
+        Ok(RegisterMap::from_raw(data))
+    }
+
+    /// Reads every register but REG09 and REG0B: REG00 - REG08 in one
+    /// transaction, REG0A in a second one. Unlike [`read_all`](Self::read_all)
+    /// this leaves the latched faults in REG09 alone. REG09 and REG0B read as
+    /// 0 in the returned map.
+    #[inline(always)]
+    pub async fn read_settings(&mut self) -> Result<RegisterMap, Error<E>> {
+        let mut data = [0u8; REGISTER_COUNT];
+        self.read_registers(Register::Reg00, &mut data[..=Register::Reg08 as usize])
+            .await?;
+        data[Register::Reg0a as usize] = self.read_register(Register::Reg0a).await?;
+        Ok(RegisterMap::from_raw(data))
+    }
+
+    /// Reads the status registers REG08 - REG0A in a single transaction.
+    ///
+    /// Reading REG09 clears its latched faults, see
+    /// [`read_faults`](Self::read_faults).
+    #[inline(always)]
+    pub async fn read_status(&mut self) -> Result<StatusRegisters, Error<E>> {
+        let mut data = [0u8; 3];
+        self.read_registers(Register::Reg08, &mut data).await?;
+        Ok(StatusRegisters::from_raw(data))
+    }
+
+    /// Reads REG08 - REG0A in a single transaction and derives the charge
+    /// state from them.
+    ///
+    /// Reading REG09 clears its latched faults, see
+    /// [`read_faults`](Self::read_faults).
+    #[inline(always)]
+    pub async fn status(&mut self) -> Result<ChargerStatus, Error<E>> {
+        let registers = self.read_status().await?;
+        Ok(ChargerStatus::from(registers))
+    }
+
+    /// Reads `register` and decodes it, failing with
+    /// [`Error::InvalidFieldValue`] if a field holds an undefined value.
+    #[inline(always)]
+    async fn read_decoded<T: TryFrom<u8>>(&mut self, register: Register) -> Result<T, Error<E>> {
+        let data = self.read_register(register).await?;
+        T::try_from(data).map_err(|_| Error::InvalidFieldValue {
+            register,
+            raw: data,
+        })
+    }
+
+    /// Reads `register` and decodes it without failing on undefined field
+    /// values. The raw byte is kept alongside the decoded value.
+    #[inline(always)]
+    pub async fn get_lenient<T: DecodeLenient>(
+        &mut self,
+        register: Register,
+    ) -> Result<Lenient<T>, Error<E>> {
+        let data = self.read_register(register).await?;
+        Ok(Lenient::from(data))
+    }
+
+    /// Reads and decodes the register `R`.
+    #[inline(always)]
+    pub async fn read<R: Readable>(&mut self) -> Result<R::Value, Error<E>> {
+        self.read_decoded(R::ADDRESS).await
+    }
+
+    /// Encodes `value` and writes it to the register `R`.
+    #[inline(always)]
+    pub async fn write<R: Writable>(&mut self, value: R::Value) -> Result<(), Error<E>> {
+        self.write_register(R::ADDRESS, value.into()).await
+    }
+
+    /// Reads the register `R`, applies `f` to the decoded value and writes
+    /// the result back if it differs from what was read.
+    ///
+    /// Returns the value before and after `f`.
+    #[inline(always)]
+    pub async fn modify<R, F>(&mut self, f: F) -> Result<Modified<R::Value>, Error<E>>
+    where
+        R: Readable + Writable,
+        F: FnOnce(&mut R::Value),
+    {
+        let old = self.read::<R>().await?;
+        let mut new = old;
+        f(&mut new);
+        if new != old {
+            self.write::<R>(new).await?;
+        }
+        Ok(Modified { old, new })
+    }
+
+    #[inline(always)]
+    pub async fn get_device_revision(&mut self) -> Result<Option<u8>, Error<E>> {
+        let values = self.get_reg0b().await?;
+
+        Ok(values.device_info().map(|info| info.device_revision))
+    }
+
+    /// Reads REG0B and returns the part number and silicon revision.
+    ///
+    /// Fails with [`Error::UnexpectedDevice`] if the chip does not identify
+    /// as an SGM41511.
+    #[inline(always)]
+    pub async fn identify(&mut self) -> Result<DeviceInfo, Error<E>> {
+        let data = self.read_register(Register::Reg0b).await?;
+        Reg0bValues::decode_lenient(data)
+            .device_info()
+            .ok_or(Error::UnexpectedDevice { raw: data })
+    }
+
+    #[inline(always)]
+    pub async fn get_reg00(&mut self) -> Result<Reg00Values, Error<E>> {
+        self.read::<Reg00Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg00(&mut self, value: Reg00Values) -> Result<(), Error<E>> {
+        self.write::<Reg00Values>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg00<F>(&mut self, f: F) -> Result<Modified<Reg00Values>, Error<E>>
+    where
+        F: FnOnce(&mut Reg00Values),
+    {
+        self.modify::<Reg00Values, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg01(&mut self) -> Result<Reg01Values, Error<E>> {
+        self.read::<Reg01Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg01(&mut self, value: Reg01Values) -> Result<(), Error<E>> {
+        self.write::<Reg01Values>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg01<F>(&mut self, f: F) -> Result<Modified<Reg01Values>, Error<E>>
+    where
+        F: FnOnce(&mut Reg01Values),
+    {
+        self.modify::<Reg01Values, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg02(&mut self) -> Result<Reg02Values, Error<E>> {
+        self.read::<Reg02Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg02(&mut self, value: Reg02Values) -> Result<(), Error<E>> {
+        self.write::<Reg02Values>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg02<F>(&mut self, f: F) -> Result<Modified<Reg02Values>, Error<E>>
+    where
+        F: FnOnce(&mut Reg02Values),
+    {
+        self.modify::<Reg02Values, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg03(&mut self) -> Result<Reg03Values, Error<E>> {
+        self.read::<Reg03Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg03(&mut self, value: Reg03Values) -> Result<(), Error<E>> {
+        self.write::<Reg03Values>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg03<F>(&mut self, f: F) -> Result<Modified<Reg03Values>, Error<E>>
+    where
+        F: FnOnce(&mut Reg03Values),
+    {
+        self.modify::<Reg03Values, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg04(&mut self) -> Result<Reg04Values, Error<E>> {
+        self.read::<Reg04Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg04(&mut self, value: Reg04Values) -> Result<(), Error<E>> {
+        self.write::<Reg04Values>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg04<F>(&mut self, f: F) -> Result<Modified<Reg04Values>, Error<E>>
+    where
+        F: FnOnce(&mut Reg04Values),
+    {
+        self.modify::<Reg04Values, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg05(&mut self) -> Result<Reg05Values, Error<E>> {
+        self.read::<Reg05Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg05(&mut self, value: Reg05Values) -> Result<(), Error<E>> {
+        self.write::<Reg05Values>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg05<F>(&mut self, f: F) -> Result<Modified<Reg05Values>, Error<E>>
+    where
+        F: FnOnce(&mut Reg05Values),
+    {
+        self.modify::<Reg05Values, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg06(&mut self) -> Result<Reg06Values, Error<E>> {
+        self.read::<Reg06Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg06(&mut self, value: Reg06Values) -> Result<(), Error<E>> {
+        self.write::<Reg06Values>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg06<F>(&mut self, f: F) -> Result<Modified<Reg06Values>, Error<E>>
+    where
+        F: FnOnce(&mut Reg06Values),
+    {
+        self.modify::<Reg06Values, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg07(&mut self) -> Result<Reg07Values, Error<E>> {
+        self.read::<Reg07Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg07(&mut self, value: Reg07Values) -> Result<(), Error<E>> {
+        self.write::<Reg07Values>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg07<F>(&mut self, f: F) -> Result<Modified<Reg07Values>, Error<E>>
+    where
+        F: FnOnce(&mut Reg07Values),
+    {
+        self.modify::<Reg07Values, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg08(&mut self) -> Result<Reg08Values, Error<E>> {
+        self.read::<Reg08Values>().await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg09(&mut self) -> Result<Reg09Values, Error<E>> {
+        self.read::<Reg09Values>().await
+    }
+
+    /// Reads REG09 twice: the first read returns and clears the latched
+    /// faults, the second the faults present now.
+    #[inline(always)]
+    pub async fn read_faults(&mut self) -> Result<FaultReport, Error<E>> {
+        let latched = self.read::<Reg09Values>().await?;
+        let current = self.read::<Reg09Values>().await?;
+        Ok(FaultReport { latched, current })
+    }
+
+    #[inline(always)]
+    pub async fn get_reg0a(&mut self) -> Result<Reg0aValues, Error<E>> {
+        self.read::<Reg0aValues>().await
+    }
+
+    #[inline(always)]
+    pub async fn set_reg0a(&mut self, value: Reg0aValues) -> Result<(), Error<E>> {
+        self.write::<Reg0aValues>(value).await
+    }
+
+    #[inline(always)]
+    pub async fn modify_reg0a<F>(&mut self, f: F) -> Result<Modified<Reg0aValues>, Error<E>>
+    where
+        F: FnOnce(&mut Reg0aValues),
+    {
+        self.modify::<Reg0aValues, _>(f).await
+    }
+
+    #[inline(always)]
+    pub async fn get_reg0b(&mut self) -> Result<Reg0bValues, Error<E>> {
+        self.read::<Reg0bValues>().await
+    }
+
+    /// Reads the VINDPM/IINDPM interrupt mask bits of REG0A.
+    #[inline(always)]
+    pub async fn get_interrupt_mask(&mut self) -> Result<InterruptMask, Error<E>> {
+        let data = self.read_register(Register::Reg0a).await?;
+        Ok(InterruptMask::from_bits_truncate(data))
+    }
+
+    /// Writes the VINDPM/IINDPM interrupt mask bits of REG0A, leaving the
+    /// other bits of the register as they are.
+    #[inline(always)]
+    pub async fn set_interrupt_mask(&mut self, mask: InterruptMask) -> Result<(), Error<E>> {
+        let data = self.read_register(Register::Reg0a).await?;
+        let value = (data & !InterruptMask::ALL.bits()) | mask.bits();
+        self.write_register(Register::Reg0a, value).await
+    }
+
+    #[deprecated(note = "use `set_interrupt_mask` instead")]
+    #[inline(always)]
+    pub async fn set_interrupt_masks(
+        &mut self,
+        vindpm: bool,
+        iindpm: bool,
+    ) -> Result<(), Error<E>> {
+        let mut mask = InterruptMask::NONE;
+        if vindpm {
+            mask.insert(InterruptMask::VINDPM);
+        }
+        if iindpm {
+            mask.insert(InterruptMask::IINDPM);
+        }
+        self.set_interrupt_mask(mask).await
+    }
+
+    /// Programs the input current limit (IINDPM) to the largest setting not above `milliamps`
+    /// and returns the programmed value.
+    ///
+    /// Fails with [`Error::Conversion`] if `milliamps` is below the lowest
+    /// setting. Use [`InputCurrentLimit::from_milliamps`] and [`modify_reg00`] for other
+    /// rounding modes.
+    ///
+    /// [`modify_reg00`]: Self::modify_reg00
+    pub async fn set_input_current_limit_ma(&mut self, milliamps: u16) -> Result<u16, Error<E>> {
+        let setting = InputCurrentLimit::from_milliamps(milliamps, Rounding::Floor)
+            .map_err(Error::Conversion)?;
+        self.modify::<Reg00Values, _>(|reg| reg.input_milliamps_limit = setting)
+            .await?;
+        Ok(setting.as_milliamps())
+    }
+
+    /// Programs the minimum system voltage, rounding down, and returns the
+    /// programmed value. See [`set_input_current_limit_ma`](Self::set_input_current_limit_ma).
+    pub async fn set_min_system_voltage_mv(&mut self, millivolts: u16) -> Result<u16, Error<E>> {
+        let setting = MinSystemVoltage::from_millivolts(millivolts, Rounding::Floor)
+            .map_err(Error::Conversion)?;
+        self.modify::<Reg01Values, _>(|reg| reg.sys_min_voltage = setting)
+            .await?;
+        Ok(setting.as_millivolts())
+    }
+
+    /// Programs the fast charge current, rounding down, and returns the
+    /// programmed value. See [`set_input_current_limit_ma`](Self::set_input_current_limit_ma).
+    pub async fn set_charge_current_ma(&mut self, milliamps: u16) -> Result<u16, Error<E>> {
+        let setting =
+            ChargeCurrent::from_milliamps(milliamps, Rounding::Floor).map_err(Error::Conversion)?;
+        self.modify::<Reg02Values, _>(|reg| reg.charge_current = setting)
+            .await?;
+        Ok(setting.as_milliamps())
+    }
+
+    /// Programs the pre-charge current, rounding down, and returns the
+    /// programmed value. See [`set_input_current_limit_ma`](Self::set_input_current_limit_ma).
+    pub async fn set_precharge_current_ma(&mut self, milliamps: u16) -> Result<u16, Error<E>> {
+        let setting = PreChargeCurrent::from_milliamps(milliamps, Rounding::Floor)
+            .map_err(Error::Conversion)?;
+        self.modify::<Reg03Values, _>(|reg| reg.pre_charge_current = setting)
+            .await?;
+        Ok(setting.as_milliamps())
+    }
+
+    /// Programs the termination current, rounding down, and returns the
+    /// programmed value. See [`set_input_current_limit_ma`](Self::set_input_current_limit_ma).
+    pub async fn set_termination_current_ma(&mut self, milliamps: u16) -> Result<u16, Error<E>> {
+        let setting = TermChargeCurrent::from_milliamps(milliamps, Rounding::Floor)
+            .map_err(Error::Conversion)?;
+        self.modify::<Reg03Values, _>(|reg| reg.term_charge_current = setting)
+            .await?;
+        Ok(setting.as_milliamps())
+    }
+
+    /// Programs the charge voltage limit, rounding down, and returns the
+    /// programmed value. See [`set_input_current_limit_ma`](Self::set_input_current_limit_ma).
+    pub async fn set_charge_voltage_mv(&mut self, millivolts: u16) -> Result<u16, Error<E>> {
+        let setting = ChargeVoltageLimit::from_millivolts(millivolts, Rounding::Floor)
+            .map_err(Error::Conversion)?;
+        self.modify::<Reg04Values, _>(|reg| reg.charge_voltage_limit = setting)
+            .await?;
+        Ok(setting.as_millivolts())
+    }
+
+    /// Programs the boost mode voltage, rounding down, and returns the
+    /// programmed value. See [`set_input_current_limit_ma`](Self::set_input_current_limit_ma).
+    pub async fn set_boost_voltage_mv(&mut self, millivolts: u16) -> Result<u16, Error<E>> {
+        let setting = BoostModeVoltage::from_millivolts(millivolts, Rounding::Floor)
+            .map_err(Error::Conversion)?;
+        self.modify::<Reg06Values, _>(|reg| reg.boost_mode_voltage = setting)
+            .await?;
+        Ok(setting.as_millivolts())
+    }
+
+    /// Programs the input voltage limit (VINDPM), rounding down, and returns the
+    /// programmed value. See [`set_input_current_limit_ma`](Self::set_input_current_limit_ma).
+    pub async fn set_vindpm_threshold_mv(&mut self, millivolts: u16) -> Result<u16, Error<E>> {
+        let setting = VINDPMThreshold::from_millivolts(millivolts, Rounding::Floor)
+            .map_err(Error::Conversion)?;
+        self.modify::<Reg06Values, _>(|reg| reg.vindpm_threshold = setting)
+            .await?;
+        Ok(setting.as_millivolts())
+    }
+
+    /// Sets WD_RST in REG01 to restart the I2C watchdog timer. The other
+    /// bits, CHG_CONFIG and OTG_CONFIG included, are written back unchanged.
+    #[inline(always)]
+    pub async fn kick_watchdog(&mut self) -> Result<(), Error<E>> {
+        self.modify_reg01(|values| values.watchdog_reset = true)
+            .await?;
+        Ok(())
+    }
+
+    /// Sets REG_RST in REG0B, which puts every register back to its
+    /// default. The other REG0B bits are read-only and written as 0.
+    #[inline(always)]
+    pub async fn reset_register(&mut self) -> Result<(), Error<E>> {
+        self.write::<Reg0bValues>(Reg0bValues {
+            reg_reset: true,
+            part_number: 0,
+            sgm_part: false,
+            device_revision: 0,
+        })
+        .await
+    }
+}
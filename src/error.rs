@@ -0,0 +1,33 @@
+use crate::units::ConversionError;
+use crate::Register;
+
+/// Errors returned by the SGM41511 driver.
+///
+/// `E` is the error type of the underlying I2C bus.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<E> {
+    /// The I2C transaction failed.
+    Bus(E),
+    /// The chip answered, but a field of `register` holds a value that is not
+    /// defined in the datasheet.
+    InvalidFieldValue { register: Register, raw: u8 },
+    /// REG0B does not identify the device as an SGM41511.
+    UnexpectedDevice { raw: u8 },
+    /// A register read back a different value than the one written.
+    VerifyFailed {
+        register: Register,
+        expected: u8,
+        actual: u8,
+    },
+    /// A physical value has no matching register setting.
+    Conversion(ConversionError),
+    /// The operation did not complete in time.
+    Timeout,
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(error: E) -> Self {
+        Error::Bus(error)
+    }
+}
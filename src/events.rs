@@ -0,0 +1,292 @@
+//! Typed events from successive status snapshots.
+//!
+//! [`EventEngine`] compares each new [`StatusSnapshot`] with the previous
+//! one and queues an [`Event`] for every change, in a fixed-capacity
+//! [`EventQueue`] that needs no allocator.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sgm41511::events::*;
+//! use sgm41511::register_map::StatusRegisters;
+//! use sgm41511::types::*;
+//! let mut engine = EventEngine::<8>::new();
+//! // DCP adapter attached, fast charging.
+//! engine.update(StatusSnapshot::from(StatusRegisters::from_raw([0x74, 0x00, 0x80])));
+//! assert_eq!(
+//!     engine.pop(),
+//!     Some(Event::InputAttached { source: VBUSStatus::USBDCP })
+//! );
+//! assert_eq!(
+//!     engine.pop(),
+//!     Some(Event::ChargePhaseChanged { from: ChargeStatus::Disabled, to: ChargeStatus::Fast })
+//! );
+//! assert_eq!(engine.pop(), None);
+//! ```
+
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+#[cfg(feature = "async")]
+use crate::driver::SGM41511Async;
+use crate::driver::SGM41511;
+use crate::faults::{Fault, FaultFlags};
+use crate::register_map::StatusRegisters;
+use crate::types::*;
+use crate::Error;
+
+/// Dynamic power management loop.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Dpm {
+    /// Input voltage regulation (VINDPM).
+    Voltage,
+    /// Input current regulation (IINDPM).
+    Current,
+}
+
+/// A change in the charger status.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Event {
+    /// An input source was plugged in.
+    InputAttached {
+        source: VBUSStatus,
+    },
+    /// The input source type changed while plugged in, for example once
+    /// detection has finished.
+    InputSourceChanged {
+        source: VBUSStatus,
+    },
+    /// The input source was removed.
+    InputDetached,
+    ChargePhaseChanged {
+        from: ChargeStatus,
+        to: ChargeStatus,
+    },
+    FaultRaised(Fault),
+    FaultCleared(Fault),
+    DpmEntered(Dpm),
+    DpmExited(Dpm),
+    ThermalRegulationEntered,
+    ThermalRegulationExited,
+    TopOffStarted,
+    TopOffEnded,
+}
+
+/// The status registers REG08 - REG0A, decoded.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StatusSnapshot {
+    pub status: Reg08Values,
+    pub faults: FaultFlags,
+    pub conditions: Reg0aValues,
+}
+
+impl StatusSnapshot {
+    /// Snapshot of a chip fresh out of reset, with no input and no fault.
+    pub const RESET: StatusSnapshot = StatusSnapshot {
+        status: Reg08Values::RESET,
+        faults: FaultFlags::NONE,
+        conditions: Reg0aValues::RESET,
+    };
+}
+
+/// Decodes leniently, reserved codes never fail a status update.
+impl From<StatusRegisters> for StatusSnapshot {
+    fn from(registers: StatusRegisters) -> Self {
+        let [reg08, reg09, reg0a] = *registers.as_raw();
+        StatusSnapshot {
+            status: Reg08Values::decode_lenient(reg08),
+            faults: FaultFlags::from(Reg09Values::decode_lenient(reg09)),
+            conditions: Reg0aValues::decode_lenient(reg0a),
+        }
+    }
+}
+
+/// Fixed-capacity FIFO of events. When full, the oldest event is dropped.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct EventQueue<const N: usize> {
+    events: [Option<Event>; N],
+    head: usize,
+    len: usize,
+    dropped: u32,
+}
+
+impl<const N: usize> Default for EventQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> EventQueue<N> {
+    pub const fn new() -> Self {
+        EventQueue {
+            events: [None; N],
+            head: 0,
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of events dropped because the queue was full.
+    pub const fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    pub fn push(&mut self, event: Event) {
+        if N == 0 {
+            self.dropped = self.dropped.saturating_add(1);
+            return;
+        }
+        if self.len == N {
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.events[(self.head + self.len) % N] = Some(event);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<Event> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.events[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        event
+    }
+}
+
+/// Turns status snapshots into [`Event`]s.
+///
+/// The first snapshot is compared with [`StatusSnapshot::RESET`], so an
+/// input already attached at start-up is reported.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct EventEngine<const N: usize> {
+    previous: StatusSnapshot,
+    queue: EventQueue<N>,
+}
+
+impl<const N: usize> Default for EventEngine<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> EventEngine<N> {
+    pub const fn new() -> Self {
+        EventEngine {
+            previous: StatusSnapshot::RESET,
+            queue: EventQueue::new(),
+        }
+    }
+
+    /// The last snapshot passed to [`update`](EventEngine::update).
+    pub const fn previous(&self) -> &StatusSnapshot {
+        &self.previous
+    }
+
+    pub fn queue(&self) -> &EventQueue<N> {
+        &self.queue
+    }
+
+    pub fn pop(&mut self) -> Option<Event> {
+        self.queue.pop()
+    }
+
+    /// Queues the events between the previous snapshot and `snapshot`.
+    pub fn update(&mut self, snapshot: StatusSnapshot) {
+        let old = self.previous;
+        let new = snapshot;
+        self.previous = snapshot;
+
+        let (old_source, new_source) = (old.status.vbus_status, new.status.vbus_status);
+        if old_source != new_source {
+            self.queue.push(match (old_source, new_source) {
+                (_, VBUSStatus::NoInput) => Event::InputDetached,
+                (VBUSStatus::NoInput, source) => Event::InputAttached { source },
+                (_, source) => Event::InputSourceChanged { source },
+            });
+        }
+
+        let (from, to) = (old.status.charge_status, new.status.charge_status);
+        if from != to {
+            self.queue.push(Event::ChargePhaseChanged { from, to });
+        }
+
+        for fault in new.faults.difference(old.faults).iter() {
+            self.queue.push(Event::FaultRaised(fault));
+        }
+        for fault in old.faults.difference(new.faults).iter() {
+            self.queue.push(Event::FaultCleared(fault));
+        }
+
+        self.edge(
+            old.conditions.vindpm_status,
+            new.conditions.vindpm_status,
+            Event::DpmEntered(Dpm::Voltage),
+            Event::DpmExited(Dpm::Voltage),
+        );
+        self.edge(
+            old.conditions.iindpm_status,
+            new.conditions.iindpm_status,
+            Event::DpmEntered(Dpm::Current),
+            Event::DpmExited(Dpm::Current),
+        );
+        self.edge(
+            old.status.therm_status,
+            new.status.therm_status,
+            Event::ThermalRegulationEntered,
+            Event::ThermalRegulationExited,
+        );
+        self.edge(
+            old.conditions.topoff_active,
+            new.conditions.topoff_active,
+            Event::TopOffStarted,
+            Event::TopOffEnded,
+        );
+    }
+
+    fn edge(&mut self, old: bool, new: bool, rising: Event, falling: Event) {
+        match (old, new) {
+            (false, true) => self.queue.push(rising),
+            (true, false) => self.queue.push(falling),
+            _ => {}
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        I2c(sync = "BlockingI2c", async = "AsyncI2c"),
+        SGM41511(sync = "SGM41511", async = "SGM41511Async"),
+        poll(sync = "poll", async = "poll_async")
+    ),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl<const N: usize> EventEngine<N> {
+    /// Reads REG08 - REG0A in one transaction and queues the events since
+    /// the previous poll. Returns the number of events queued.
+    pub async fn poll<I2C, E>(&mut self, device: &mut SGM41511<I2C>) -> Result<usize, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let registers = device.read_status().await?;
+        let (len, dropped) = (self.queue.len(), self.queue.dropped());
+        self.update(StatusSnapshot::from(registers));
+        Ok(self.queue.len() - len + (self.queue.dropped() - dropped) as usize)
+    }
+}
@@ -0,0 +1,171 @@
+//! Latched and present fault conditions.
+//!
+//! The fault bits of REG09 latch: the first read after a fault returns it
+//! even if the condition is already gone, and clears the latch. A second
+//! read returns the condition as it is now. [`FaultReport`] holds both reads.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sgm41511::faults::*;
+//! use sgm41511::types::*;
+//! let report = FaultReport {
+//!     latched: Reg09Values::try_from(0b1000_0101).unwrap(),
+//!     current: Reg09Values::try_from(0b0000_0101).unwrap(),
+//! };
+//! assert_eq!(
+//!     report.new_faults().iter().collect::<Vec<_>>(),
+//!     [Fault::Watchdog, Fault::NtcCold]
+//! );
+//! assert_eq!(report.cleared_faults().iter().collect::<Vec<_>>(), [Fault::Watchdog]);
+//! assert_eq!(report.active_faults().iter().collect::<Vec<_>>(), [Fault::NtcCold]);
+//! ```
+
+use crate::types::*;
+
+/// A single fault condition of REG09.
+#[repr(u8)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Fault {
+    /// The I2C watchdog timer expired.
+    Watchdog,
+    /// VBUS overloaded or over-voltage in OTG mode, or battery too low.
+    Boost,
+    /// Input over-voltage or poor source.
+    Input,
+    ThermalShutdown,
+    /// The fast charge safety timer expired.
+    SafetyTimer,
+    /// Battery over-voltage.
+    Battery,
+    NtcWarm,
+    NtcCool,
+    NtcCold,
+    NtcHot,
+}
+
+impl Fault {
+    /// Every fault, in REG09 bit order.
+    pub const ALL: [Fault; 10] = [
+        Fault::Watchdog,
+        Fault::Boost,
+        Fault::Input,
+        Fault::ThermalShutdown,
+        Fault::SafetyTimer,
+        Fault::Battery,
+        Fault::NtcWarm,
+        Fault::NtcCool,
+        Fault::NtcCold,
+        Fault::NtcHot,
+    ];
+}
+
+impl From<Fault> for &'static str {
+    fn from(value: Fault) -> Self {
+        match value {
+            Fault::Watchdog => "Watchdog",
+            Fault::Boost => "Boost",
+            Fault::Input => "Input",
+            Fault::ThermalShutdown => "ThermalShutdown",
+            Fault::SafetyTimer => "SafetyTimer",
+            Fault::Battery => "Battery",
+            Fault::NtcWarm => "NtcWarm",
+            Fault::NtcCool => "NtcCool",
+            Fault::NtcCold => "NtcCold",
+            Fault::NtcHot => "NtcHot",
+        }
+    }
+}
+
+/// A set of [`Fault`]s.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FaultFlags(u16);
+
+impl FaultFlags {
+    pub const NONE: FaultFlags = FaultFlags(0);
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn contains(&self, fault: Fault) -> bool {
+        self.0 & (1 << fault as u8) != 0
+    }
+
+    pub fn insert(&mut self, fault: Fault) {
+        self.0 |= 1 << fault as u8;
+    }
+
+    /// Faults in `self` that are not in `other`.
+    pub const fn difference(&self, other: FaultFlags) -> FaultFlags {
+        FaultFlags(self.0 & !other.0)
+    }
+
+    /// Iterates over the faults in the set, in REG09 bit order.
+    pub fn iter(&self) -> impl Iterator<Item = Fault> + '_ {
+        Fault::ALL.into_iter().filter(|fault| self.contains(*fault))
+    }
+}
+
+impl From<Reg09Values> for FaultFlags {
+    fn from(values: Reg09Values) -> Self {
+        let mut flags = FaultFlags::NONE;
+        if values.watchdog_fault {
+            flags.insert(Fault::Watchdog);
+        }
+        if values.boost_fault {
+            flags.insert(Fault::Boost);
+        }
+        match values.charge_fault {
+            ChargeFault::Normal => {}
+            ChargeFault::InputFault => flags.insert(Fault::Input),
+            ChargeFault::ThermalShutdown => flags.insert(Fault::ThermalShutdown),
+            ChargeFault::ChhargeSafetyTimerExpired => flags.insert(Fault::SafetyTimer),
+        }
+        if values.bat_fault {
+            flags.insert(Fault::Battery);
+        }
+        match values.ntc_fault {
+            NtcFault::Normal => {}
+            NtcFault::Warm => flags.insert(Fault::NtcWarm),
+            NtcFault::Cool => flags.insert(Fault::NtcCool),
+            NtcFault::Cold => flags.insert(Fault::NtcCold),
+            NtcFault::Hot => flags.insert(Fault::NtcHot),
+        }
+        flags
+    }
+}
+
+/// Two back-to-back reads of REG09.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FaultReport {
+    /// First read: every fault that occurred since the previous read.
+    pub latched: Reg09Values,
+    /// Second read: the faults present now.
+    pub current: Reg09Values,
+}
+
+impl FaultReport {
+    /// Faults that occurred since REG09 was last read, whether or not they
+    /// are still present.
+    pub fn new_faults(&self) -> FaultFlags {
+        FaultFlags::from(self.latched)
+    }
+
+    /// Faults that occurred since the last read but are already gone.
+    pub fn cleared_faults(&self) -> FaultFlags {
+        self.new_faults().difference(self.active_faults())
+    }
+
+    /// Faults present now.
+    pub fn active_faults(&self) -> FaultFlags {
+        FaultFlags::from(self.current)
+    }
+}
@@ -0,0 +1,88 @@
+//! Event waiting on the INT pin.
+//!
+//! The chip pulls INT low for 256 us whenever a status or fault bit
+//! changes. [`InterruptPin`] waits for that falling edge instead of polling,
+//! then reads REG08 - REG0A and reports what changed. VINDPM and IINDPM
+//! changes only pulse INT when unmasked in REG0A, so masked DPM events are
+//! left out of the reasons.
+
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::i2c::I2c;
+
+use crate::driver::SGM41511Async;
+use crate::events::{Dpm, Event, EventEngine, EventQueue, StatusSnapshot};
+use crate::Error;
+
+/// Error of [`InterruptPin::wait_for_event`].
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InterruptError<E, PE> {
+    /// Reading the status registers failed.
+    Device(Error<E>),
+    /// Waiting on the INT pin failed.
+    Pin(PE),
+}
+
+/// What an INT pulse was about.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Interrupt<const N: usize> {
+    /// The status read after the pulse.
+    pub status: StatusSnapshot,
+    /// The changes since the previous pulse. Empty if the pulse was for a
+    /// fault that had already cleared by the time it was read.
+    pub reasons: EventQueue<N>,
+}
+
+/// The INT pin of the charger, with the state needed to decode its pulses.
+pub struct InterruptPin<P, const N: usize> {
+    pin: P,
+    engine: EventEngine<N>,
+}
+
+impl<P: Wait, const N: usize> InterruptPin<P, N> {
+    pub fn new(pin: P) -> Self {
+        InterruptPin {
+            pin,
+            engine: EventEngine::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> P {
+        self.pin
+    }
+
+    /// Waits for the next falling edge on INT, then reads REG08 - REG0A in
+    /// one transaction and returns the decoded reasons.
+    pub async fn wait_for_event<I2C, E>(
+        &mut self,
+        device: &mut SGM41511Async<I2C>,
+    ) -> Result<Interrupt<N>, InterruptError<E, P::Error>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        self.pin
+            .wait_for_falling_edge()
+            .await
+            .map_err(InterruptError::Pin)?;
+        let registers = device.read_status().await.map_err(InterruptError::Device)?;
+        let status = StatusSnapshot::from(registers);
+        self.engine.update(status);
+
+        let mut reasons = EventQueue::new();
+        while let Some(event) = self.engine.pop() {
+            let masked = match event {
+                Event::DpmEntered(Dpm::Voltage) | Event::DpmExited(Dpm::Voltage) => {
+                    status.conditions.vindpm_int_mask
+                }
+                Event::DpmEntered(Dpm::Current) | Event::DpmExited(Dpm::Current) => {
+                    status.conditions.iindpm_int_mask
+                }
+                _ => false,
+            };
+            if !masked {
+                reasons.push(event);
+            }
+        }
+        Ok(Interrupt { status, reasons })
+    }
+}
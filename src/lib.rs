@@ -1,18 +1,52 @@
 #![no_std]
 
+#[cfg(feature = "sim")]
+extern crate std;
+
+mod cache;
+pub mod config;
+mod driver;
+pub mod error;
+pub mod events;
+pub mod faults;
+#[cfg(feature = "async")]
+pub mod interrupt;
+pub mod recovery;
+pub mod register_map;
+pub mod registers;
+pub mod retry;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod status;
 pub mod types;
+pub mod units;
+pub mod validate;
+pub mod watchdog;
+pub use error::Error;
 use types::*;
 
-#[cfg(not(feature = "async"))]
-use embedded_hal::i2c::I2c;
 use embedded_hal::i2c::SevenBitAddress;
+
+/// Blocking driver, built on [`embedded_hal::i2c::I2c`].
+pub mod blocking {
+    pub use crate::cache::CachedSGM41511;
+    pub use crate::driver::SGM41511;
+}
+
+/// Async driver, built on [`embedded_hal_async::i2c::I2c`].
 #[cfg(feature = "async")]
-use embedded_hal_async::i2c::I2c;
-use types::Reg00Values;
+pub mod asynch {
+    pub use crate::cache::CachedSGM41511Async as CachedSGM41511;
+    pub use crate::driver::SGM41511Async as SGM41511;
+}
+
+pub use blocking::SGM41511;
 
 pub const SGM41511_ADDR: SevenBitAddress = 0x6B;
 
 #[repr(u8)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Register {
     Reg00 = 0x00,
     Reg01 = 0x01,
@@ -57,9 +91,9 @@ impl From<u8> for Voltage {
     }
 }
 
-impl<'a> Into<&'a str> for Voltage {
-    fn into(self) -> &'a str {
-        match self {
+impl From<Voltage> for &'static str {
+    fn from(value: Voltage) -> Self {
+        match value {
             Voltage::Unattached => "Unattached",
             Voltage::_5v => "5V",
             Voltage::_9v => "9V",
@@ -94,33 +128,35 @@ pub enum Current {
     _5_0a = 0x0F,
 }
 
-impl From<u8> for Current {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Current {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => Current::_0_5a,
-            0x01 => Current::_0_7a,
-            0x02 => Current::_1_0a,
-            0x03 => Current::_1_25a,
-            0x04 => Current::_1_5a,
-            0x05 => Current::_1_75a,
-            0x06 => Current::_2_0a,
-            0x07 => Current::_2_25a,
-            0x08 => Current::_2_5a,
-            0x09 => Current::_2_75a,
-            0x0A => Current::_3_0a,
-            0x0B => Current::_3_25a,
-            0x0C => Current::_3_5a,
-            0x0D => Current::_4_0a,
-            0x0E => Current::_4_5a,
-            0x0F => Current::_5_0a,
-            _ => unreachable!(),
+            0x00 => Ok(Current::_0_5a),
+            0x01 => Ok(Current::_0_7a),
+            0x02 => Ok(Current::_1_0a),
+            0x03 => Ok(Current::_1_25a),
+            0x04 => Ok(Current::_1_5a),
+            0x05 => Ok(Current::_1_75a),
+            0x06 => Ok(Current::_2_0a),
+            0x07 => Ok(Current::_2_25a),
+            0x08 => Ok(Current::_2_5a),
+            0x09 => Ok(Current::_2_75a),
+            0x0A => Ok(Current::_3_0a),
+            0x0B => Ok(Current::_3_25a),
+            0x0C => Ok(Current::_3_5a),
+            0x0D => Ok(Current::_4_0a),
+            0x0E => Ok(Current::_4_5a),
+            0x0F => Ok(Current::_5_0a),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
 
-impl<'a> Into<&'a str> for Current {
-    fn into(self) -> &'a str {
-        match self {
+impl From<Current> for &'static str {
+    fn from(value: Current) -> Self {
+        match value {
             Current::_0_5a => "0.5A",
             Current::_0_7a => "0.7A",
             Current::_1_0a => "1.0A",
@@ -170,9 +206,9 @@ impl From<u8> for SrcPdo {
     }
 }
 
-impl<'a> Into<&'a str> for SrcPdo {
-    fn into(self) -> &'a str {
-        match self {
+impl From<SrcPdo> for &'static str {
+    fn from(value: SrcPdo) -> Self {
+        match value {
             SrcPdo::NotSelected => "NotSelected",
             SrcPdo::_5v => "5V",
             SrcPdo::_9v => "9V",
@@ -184,168 +220,3 @@ impl<'a> Into<&'a str> for SrcPdo {
         }
     }
 }
-
-pub struct SGM41511<I2C> {
-    i2c: I2C,
-}
-
-#[maybe_async_cfg::maybe(
-    sync(cfg(not(feature = "async")), self = "SGM41511",),
-    async(feature = "async", keep_self)
-)]
-impl<I2C, E> SGM41511<I2C>
-where
-    I2C: I2c<Error = E>,
-{
-    pub fn new(i2c: I2C) -> Self {
-        Self { i2c }
-    }
-
-    #[inline(always)]
-    pub async fn read_register(&mut self, register: Register) -> Result<u8, E> {
-        let mut data = [0u8; 1];
-        self.i2c
-            .write_read(SGM41511_ADDR, &[register as u8], &mut data)
-            .await?;
-        Ok(data[0])
-    }
-
-    #[inline(always)]
-    pub async fn write_register(&mut self, register: Register, value: u8) -> Result<(), E> {
-        self.i2c
-            .write(SGM41511_ADDR, &[register as u8, value])
-            .await
-    }
-
-    #[inline(always)]
-    pub async fn get_device_revision(&mut self) -> Result<Option<u8>, E> {
-        let data = self.read_register(Register::Reg0b).await?;
-
-        if data & 0x7c == 0x14 {
-            return Ok(Some(data & 0x03));
-        }
-
-        Ok(None)
-    }
-
-    #[inline(always)]
-    pub async fn get_reg00(&mut self) -> Result<Reg00Values, E> {
-        let data = self.read_register(Register::Reg00).await?;
-        Ok(Reg00Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg00(&mut self, value: Reg00Values) -> Result<(), E> {
-        self.write_register(Register::Reg00, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn get_reg01(&mut self) -> Result<Reg01Values, E> {
-        let data = self.read_register(Register::Reg01).await?;
-        Ok(Reg01Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg01(&mut self, value: Reg01Values) -> Result<(), E> {
-        self.write_register(Register::Reg01, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn get_reg02(&mut self) -> Result<Reg02Values, E> {
-        let data = self.read_register(Register::Reg02).await?;
-        Ok(Reg02Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg02(&mut self, value: Reg02Values) -> Result<(), E> {
-        self.write_register(Register::Reg02, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn get_reg03(&mut self) -> Result<Reg03Values, E> {
-        let data = self.read_register(Register::Reg03).await?;
-        Ok(Reg03Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg03(&mut self, value: Reg03Values) -> Result<(), E> {
-        self.write_register(Register::Reg03, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn get_reg04(&mut self) -> Result<Reg04Values, E> {
-        let data = self.read_register(Register::Reg04).await?;
-        Ok(Reg04Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg04(&mut self, value: Reg04Values) -> Result<(), E> {
-        self.write_register(Register::Reg04, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn get_reg05(&mut self) -> Result<Reg05Values, E> {
-        let data = self.read_register(Register::Reg05).await?;
-        Ok(Reg05Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg05(&mut self, value: Reg05Values) -> Result<(), E> {
-        self.write_register(Register::Reg05, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn get_reg06(&mut self) -> Result<Reg06Values, E> {
-        let data = self.read_register(Register::Reg06).await?;
-        Ok(Reg06Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg06(&mut self, value: Reg06Values) -> Result<(), E> {
-        self.write_register(Register::Reg06, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn get_reg07(&mut self) -> Result<Reg07Values, E> {
-        let data = self.read_register(Register::Reg07).await?;
-        Ok(Reg07Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg07(&mut self, value: Reg07Values) -> Result<(), E> {
-        self.write_register(Register::Reg07, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn get_reg08(&mut self) -> Result<Reg08Values, E> {
-        let data = self.read_register(Register::Reg08).await?;
-        Ok(Reg08Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn get_reg09(&mut self) -> Result<Reg09Values, E> {
-        let data = self.read_register(Register::Reg09).await?;
-        Ok(Reg09Values::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn get_reg0a(&mut self) -> Result<Reg0aValues, E> {
-        let data = self.read_register(Register::Reg0a).await?;
-        Ok(Reg0aValues::from(data))
-    }
-
-    #[inline(always)]
-    pub async fn set_reg0a(&mut self, value: Reg0aValues) -> Result<(), E> {
-        self.write_register(Register::Reg0a, value.into()).await
-    }
-
-    #[inline(always)]
-    pub async fn set_interrupt_masks(&mut self, vindpm: bool, iindpm: bool) -> Result<(), E> {
-        self.write_register(Register::Reg0b, (vindpm as u8) << 1 | (iindpm as u8)).await
-    }
-
-    #[inline(always)]
-    pub async fn reset_register(&mut self) -> Result<(), E> {
-        self.write_register(Register::Reg0b, 0x80).await
-    }
-}
@@ -0,0 +1,182 @@
+//! Detection of unexpected chip resets.
+//!
+//! When the I2C watchdog expires, or the supply browns out, the SGM41511
+//! falls back to its default settings. [`ResetMonitor`] keeps the intended
+//! [`ChargerConfig`], notices when the chip no longer holds it and applies it
+//! again.
+
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::config::{ChargerConfig, Mismatches};
+#[cfg(feature = "async")]
+use crate::driver::SGM41511Async;
+use crate::driver::SGM41511;
+use crate::faults::FaultFlags;
+use crate::register_map::RegisterMap;
+use crate::types::*;
+use crate::{Error, Register};
+
+/// Suspected reason for a chip reset.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ResetCause {
+    /// WATCHDOG_FAULT was set in REG09: the host did not kick the I2C
+    /// watchdog in time.
+    WatchdogExpired,
+    /// The settings are back at their power-on values without a watchdog
+    /// fault: a power cycle, a brownout or a REG_RST write.
+    PowerOnReset,
+}
+
+/// Reported by [`ResetMonitor`] when the chip lost its configuration.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChipResetDetected {
+    pub cause: ResetCause,
+    /// The fields that no longer matched the configuration last applied.
+    /// The stored configuration has been written again.
+    pub reverted: Mismatches,
+    /// The faults latched in REG09 when the reset was found. Reading REG09
+    /// cleared them, so this is the only report of them.
+    pub faults: FaultFlags,
+}
+
+/// Keeps the chip at a stored configuration across resets.
+///
+/// Call [`check`](ResetMonitor::check) periodically, for example after each
+/// watchdog kick or on every charger interrupt.
+///
+/// Resets are told by comparing the chip with the configuration last
+/// applied, which may be older than the stored one after
+/// [`set_config`](ResetMonitor::set_config).
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ResetMonitor {
+    config: ChargerConfig,
+    applied: ChargerConfig,
+}
+
+impl ResetMonitor {
+    /// Watches a chip that `config` was already applied to. Otherwise apply
+    /// it first, with [`apply`](ResetMonitor::apply) for example, or the
+    /// settings still at their power-on values count as reset.
+    pub const fn new(config: ChargerConfig) -> Self {
+        ResetMonitor {
+            config,
+            applied: config,
+        }
+    }
+
+    pub const fn config(&self) -> &ChargerConfig {
+        &self.config
+    }
+
+    /// The configuration the chip is expected to hold.
+    pub const fn applied_config(&self) -> &ChargerConfig {
+        &self.applied
+    }
+
+    /// Replaces the stored configuration. It is applied on the next reset,
+    /// or by [`apply`](ResetMonitor::apply), not right away. Until then the
+    /// chip is still expected to hold the configuration applied before.
+    pub fn set_config(&mut self, config: ChargerConfig) {
+        self.config = config;
+    }
+
+    /// Looks for a reset in a register snapshot.
+    ///
+    /// The chip counts as reset if some settings differ from the applied
+    /// configuration and all of them are back at their power-on value.
+    /// Settings changed to anything else are left alone. WATCHDOG_FAULT in
+    /// REG09 then tells the cause.
+    pub fn detect(&self, map: &RegisterMap) -> Result<Option<ChipResetDetected>, InvalidValue> {
+        let reverted = self
+            .reverted_in(map)
+            .map_err(|register| InvalidValue(map.raw(register)))?;
+        Ok(reverted.map(|reverted| Self::detected(reverted, map.raw(Register::Reg09))))
+    }
+
+    /// Returns the settings that differ from the applied configuration if
+    /// they are all back at their power-on value.
+    fn reverted_in(&self, map: &RegisterMap) -> Result<Option<Mismatches>, Register> {
+        let actual = ChargerConfig::decode_map(map)?;
+        let reverted = self.applied.diff(&actual);
+        let changed = actual.diff_from_reset();
+        let reset = !reverted.is_empty() && reverted.iter().all(|field| !changed.contains(field));
+        Ok(reset.then_some(reverted))
+    }
+
+    fn detected(reverted: Mismatches, reg09: u8) -> ChipResetDetected {
+        let faults = Reg09Values::decode_lenient(reg09);
+        let cause = if faults.watchdog_fault {
+            ResetCause::WatchdogExpired
+        } else {
+            ResetCause::PowerOnReset
+        };
+        ChipResetDetected {
+            cause,
+            reverted,
+            faults: FaultFlags::from(faults),
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        I2c(sync = "BlockingI2c", async = "AsyncI2c"),
+        SGM41511(sync = "SGM41511", async = "SGM41511Async"),
+        apply(sync = "apply", async = "apply_async"),
+        apply_from(sync = "apply_from", async = "apply_from_async"),
+        check(sync = "check", async = "check_async")
+    ),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl ResetMonitor {
+    /// Applies the stored configuration now, see
+    /// [`ChargerConfig::apply`], and expects the chip to hold it from then
+    /// on.
+    pub async fn apply<I2C, E>(&mut self, device: &mut SGM41511<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        ChargerConfig::apply(&self.config, device).await?;
+        self.applied = self.config;
+        Ok(())
+    }
+
+    /// Reads the settings and, if the chip was reset, applies the stored
+    /// configuration, writing only the registers that differ from it.
+    ///
+    /// REG09 is only read once a reset was found, to tell its cause, so the
+    /// latched faults stay in place for the fault and event handling the
+    /// rest of the time. A reset that left every setting at the stored
+    /// configuration is not noticed; there is nothing to repair.
+    pub async fn check<I2C, E>(
+        &mut self,
+        device: &mut SGM41511<I2C>,
+    ) -> Result<Option<ChipResetDetected>, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let map = device.read_settings().await?;
+        let reverted = self
+            .reverted_in(&map)
+            .map_err(|register| Error::InvalidFieldValue {
+                register,
+                raw: map.raw(register),
+            })?;
+        match reverted {
+            Some(reverted) => {
+                let reg09 = device.read_register(Register::Reg09).await?;
+                ChargerConfig::apply_from(&self.config, device, &map).await?;
+                self.applied = self.config;
+                Ok(Some(Self::detected(reverted, reg09)))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::config::{ChargerConfig, Mismatches};
+use crate::types::*;
+use crate::Register;
+
+/// Number of registers in the SGM41511 register map (REG00 - REG0B).
+pub const REGISTER_COUNT: usize = 12;
+
+/// Snapshot of the whole register map, REG00 - REG0B.
+///
+/// Holds the raw bytes as read from the chip. Each register is decoded only
+/// when its accessor is called.
+///
+/// # Examples
+///
+/// ```rust
+/// use sgm41511::register_map::RegisterMap;
+/// use sgm41511::types::*;
+/// use sgm41511::Register;
+/// let map = RegisterMap::from_raw([
+///     0x17, 0x1a, 0xa2, 0x22, 0x58, 0x9f, 0x66, 0x4c, 0x00, 0x00, 0x00, 0x15,
+/// ]);
+/// assert_eq!(map.raw(Register::Reg02), 0xa2);
+/// assert_eq!(map.reg02().unwrap().charge_current, ChargeCurrent::_2040mA);
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterMap {
+    raw: [u8; REGISTER_COUNT],
+}
+
+impl RegisterMap {
+    /// Register values after power-on reset. REG08 - REG0A assume no input
+    /// and no fault, REG0B assumes device revision 0.
+    pub const RESET: RegisterMap = RegisterMap::from_raw([
+        0x17, 0x1a, 0xa2, 0x22, 0x58, 0x9f, 0x66, 0x4c, 0x00, 0x00, 0x00, 0x14,
+    ]);
+
+    pub const fn from_raw(raw: [u8; REGISTER_COUNT]) -> Self {
+        RegisterMap { raw }
+    }
+
+    /// Returns the raw bytes, indexed by register address.
+    pub const fn as_raw(&self) -> &[u8; REGISTER_COUNT] {
+        &self.raw
+    }
+
+    pub const fn raw(&self, register: Register) -> u8 {
+        self.raw[register as usize]
+    }
+
+    pub fn lenient<T: DecodeLenient>(&self, register: Register) -> Lenient<T> {
+        Lenient::from(self.raw(register))
+    }
+
+    pub fn reg00(&self) -> Result<Reg00Values, InvalidValue> {
+        Reg00Values::try_from(self.raw(Register::Reg00))
+    }
+
+    pub fn reg01(&self) -> Result<Reg01Values, InvalidValue> {
+        Reg01Values::try_from(self.raw(Register::Reg01))
+    }
+
+    pub fn reg02(&self) -> Result<Reg02Values, InvalidValue> {
+        Reg02Values::try_from(self.raw(Register::Reg02))
+    }
+
+    pub fn reg03(&self) -> Result<Reg03Values, InvalidValue> {
+        Reg03Values::try_from(self.raw(Register::Reg03))
+    }
+
+    pub fn reg04(&self) -> Result<Reg04Values, InvalidValue> {
+        Reg04Values::try_from(self.raw(Register::Reg04))
+    }
+
+    pub fn reg05(&self) -> Result<Reg05Values, InvalidValue> {
+        Reg05Values::try_from(self.raw(Register::Reg05))
+    }
+
+    pub fn reg06(&self) -> Result<Reg06Values, InvalidValue> {
+        Reg06Values::try_from(self.raw(Register::Reg06))
+    }
+
+    pub fn reg07(&self) -> Result<Reg07Values, InvalidValue> {
+        Reg07Values::try_from(self.raw(Register::Reg07))
+    }
+
+    pub fn reg08(&self) -> Result<Reg08Values, InvalidValue> {
+        Reg08Values::try_from(self.raw(Register::Reg08))
+    }
+
+    pub fn reg09(&self) -> Result<Reg09Values, InvalidValue> {
+        Reg09Values::try_from(self.raw(Register::Reg09))
+    }
+
+    pub fn reg0a(&self) -> Result<Reg0aValues, InvalidValue> {
+        Reg0aValues::try_from(self.raw(Register::Reg0a))
+    }
+
+    pub fn reg0b(&self) -> Result<Reg0bValues, InvalidValue> {
+        Reg0bValues::try_from(self.raw(Register::Reg0b))
+    }
+
+    /// Returns the configuration fields of REG00 - REG07 and REG0A that
+    /// deviate from their power-on reset value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sgm41511::config::ConfigField;
+    /// use sgm41511::register_map::RegisterMap;
+    /// let mut raw = *RegisterMap::RESET.as_raw();
+    /// raw[0x05] = 0x8f; // watchdog disabled
+    /// let deviations = RegisterMap::from_raw(raw).diff_from_reset().unwrap();
+    /// assert_eq!(deviations.iter().collect::<Vec<_>>(), [ConfigField::WatchdogTimerSetting]);
+    /// ```
+    pub fn diff_from_reset(&self) -> Result<Mismatches, InvalidValue> {
+        Ok(ChargerConfig::from_register_map(self)?.diff_from_reset())
+    }
+
+    /// Returns the status part of the snapshot (REG08 - REG0A).
+    pub const fn status(&self) -> StatusRegisters {
+        StatusRegisters::from_raw([self.raw[0x08], self.raw[0x09], self.raw[0x0a]])
+    }
+}
+
+/// Snapshot of the status registers, REG08 - REG0A.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StatusRegisters {
+    raw: [u8; 3],
+}
+
+impl StatusRegisters {
+    pub const fn from_raw(raw: [u8; 3]) -> Self {
+        StatusRegisters { raw }
+    }
+
+    /// Returns the raw bytes of REG08, REG09 and REG0A.
+    pub const fn as_raw(&self) -> &[u8; 3] {
+        &self.raw
+    }
+
+    pub fn reg08(&self) -> Result<Reg08Values, InvalidValue> {
+        Reg08Values::try_from(self.raw[0])
+    }
+
+    pub fn reg09(&self) -> Result<Reg09Values, InvalidValue> {
+        Reg09Values::try_from(self.raw[1])
+    }
+
+    pub fn reg0a(&self) -> Result<Reg0aValues, InvalidValue> {
+        Reg0aValues::try_from(self.raw[2])
+    }
+}
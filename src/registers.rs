@@ -0,0 +1,118 @@
+//! Type-level description of the register map.
+//!
+//! Every `RegXXValues` struct implements [`Register`], which ties it to its
+//! address, plus [`Readable`] and/or [`Writable`]. The generic
+//! [`read`](crate::SGM41511::read) and [`write`](crate::SGM41511::write)
+//! methods of the driver only accept registers with the matching marker, so
+//! writing the read-only REG08 or REG09 does not compile:
+//!
+//! ```compile_fail
+//! # use embedded_hal_mock::eh1::i2c::Mock;
+//! # use sgm41511::{types::*, SGM41511};
+//! # let mut device = SGM41511::new(Mock::new(&[]));
+//! let status: Reg08Values = device.read::<Reg08Values>().unwrap();
+//! device.write::<Reg08Values>(status).unwrap();
+//! ```
+
+use crate::types::*;
+
+/// A register of the SGM41511.
+pub trait Register {
+    /// Address of the register.
+    const ADDRESS: crate::Register;
+
+    /// Decoded value of the register. For the `RegXXValues` structs this is
+    /// the struct itself.
+    type Value: Copy + PartialEq + Into<u8> + TryFrom<u8, Error = InvalidValue> + DecodeLenient;
+}
+
+/// Marker for registers that can be read.
+pub trait Readable: Register {}
+
+/// Marker for registers that can be written.
+pub trait Writable: Register {}
+
+impl Register for Reg00Values {
+    const ADDRESS: crate::Register = crate::Register::Reg00;
+    type Value = Self;
+}
+impl Readable for Reg00Values {}
+impl Writable for Reg00Values {}
+
+impl Register for Reg01Values {
+    const ADDRESS: crate::Register = crate::Register::Reg01;
+    type Value = Self;
+}
+impl Readable for Reg01Values {}
+impl Writable for Reg01Values {}
+
+impl Register for Reg02Values {
+    const ADDRESS: crate::Register = crate::Register::Reg02;
+    type Value = Self;
+}
+impl Readable for Reg02Values {}
+impl Writable for Reg02Values {}
+
+impl Register for Reg03Values {
+    const ADDRESS: crate::Register = crate::Register::Reg03;
+    type Value = Self;
+}
+impl Readable for Reg03Values {}
+impl Writable for Reg03Values {}
+
+impl Register for Reg04Values {
+    const ADDRESS: crate::Register = crate::Register::Reg04;
+    type Value = Self;
+}
+impl Readable for Reg04Values {}
+impl Writable for Reg04Values {}
+
+impl Register for Reg05Values {
+    const ADDRESS: crate::Register = crate::Register::Reg05;
+    type Value = Self;
+}
+impl Readable for Reg05Values {}
+impl Writable for Reg05Values {}
+
+impl Register for Reg06Values {
+    const ADDRESS: crate::Register = crate::Register::Reg06;
+    type Value = Self;
+}
+impl Readable for Reg06Values {}
+impl Writable for Reg06Values {}
+
+impl Register for Reg07Values {
+    const ADDRESS: crate::Register = crate::Register::Reg07;
+    type Value = Self;
+}
+impl Readable for Reg07Values {}
+impl Writable for Reg07Values {}
+
+/// Status Bits, Read Only
+impl Register for Reg08Values {
+    const ADDRESS: crate::Register = crate::Register::Reg08;
+    type Value = Self;
+}
+impl Readable for Reg08Values {}
+
+/// Fault Bits, Read Only
+impl Register for Reg09Values {
+    const ADDRESS: crate::Register = crate::Register::Reg09;
+    type Value = Self;
+}
+impl Readable for Reg09Values {}
+
+impl Register for Reg0aValues {
+    const ADDRESS: crate::Register = crate::Register::Reg0a;
+    type Value = Self;
+}
+impl Readable for Reg0aValues {}
+impl Writable for Reg0aValues {}
+
+/// Only REG_RST is writable, the part information bits are read only.
+impl Register for Reg0bValues {
+    const ADDRESS: crate::Register = crate::Register::Reg0b;
+    type Value = Self;
+}
+impl Readable for Reg0bValues {}
+impl Writable for Reg0bValues {}
@@ -0,0 +1,345 @@
+//! Retrying of I2C transactions on transient bus errors.
+//!
+//! [`RetryI2c`] wraps the bus handed to the driver, so every register access
+//! goes through the same [`RetryPolicy`]: a failed transaction is repeated
+//! after a backoff delay, as long as its error kind is retryable and neither
+//! the attempts nor the backoff budget are used up. The last error is
+//! returned unchanged.
+//!
+//! Reads of the charger that set the register pointer and cover REG09, like
+//! the one of `read_faults` or `read_all`, are never repeated. The chip may
+//! have sent REG09 before the transaction failed, and sending it clears the
+//! latched faults, so a second attempt could report them as gone. Such
+//! failures are counted in [`RetryStats::fault_reads_not_retried`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use embedded_hal::i2c::ErrorKind;
+//! use embedded_hal_mock::eh1::delay::NoopDelay;
+//! use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+//! use sgm41511::retry::{RetryI2c, RetryPolicy};
+//! use sgm41511::{Register, SGM41511, SGM41511_ADDR};
+//! let expectations = [
+//!     Transaction::write_read(SGM41511_ADDR, vec![0x0b], vec![0x00])
+//!         .with_error(ErrorKind::ArbitrationLoss),
+//!     Transaction::write_read(SGM41511_ADDR, vec![0x0b], vec![0x14]),
+//! ];
+//! let mut i2c = Mock::new(&expectations);
+//! let bus = RetryI2c::new(i2c.clone(), NoopDelay, RetryPolicy::DEFAULT);
+//! let mut device = SGM41511::new(bus);
+//! assert_eq!(device.read_register(Register::Reg0b).unwrap(), 0x14);
+//! i2c.done();
+//! ```
+
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs as AsyncDelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use embedded_hal::delay::DelayNs as BlockingDelayNs;
+use embedded_hal::i2c::I2c as BlockingI2c;
+use embedded_hal::i2c::{ErrorKind, ErrorType, Operation, SevenBitAddress};
+
+use crate::{Register, SGM41511_ADDR};
+
+/// When and how often a failed transaction is repeated.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Attempts per transaction, the first one included. 0 counts as 1.
+    pub max_attempts: u8,
+    /// Delay before the first retry.
+    pub initial_backoff_us: u32,
+    /// Factor applied to the delay after each retry, 1 for a constant delay.
+    pub backoff_multiplier: u32,
+    /// Upper bound of the delay.
+    pub max_backoff_us: u32,
+    /// Upper bound of the delays of one transaction added up. A retry that
+    /// would wait beyond it is not made.
+    pub max_total_backoff_us: u32,
+    /// Decides from the error kind whether a retry may help.
+    pub retryable: fn(ErrorKind) -> bool,
+}
+
+impl RetryPolicy {
+    /// Every transaction is attempted once.
+    pub const NONE: Self = RetryPolicy {
+        max_attempts: 1,
+        initial_backoff_us: 0,
+        backoff_multiplier: 1,
+        max_backoff_us: 0,
+        max_total_backoff_us: 0,
+        retryable: RetryPolicy::is_transient,
+    };
+
+    /// Three attempts, 100 µs apart and then 200 µs, on transient errors.
+    /// A transaction waits 10 ms at most.
+    pub const DEFAULT: Self = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff_us: 100,
+        backoff_multiplier: 2,
+        max_backoff_us: 10_000,
+        max_total_backoff_us: 10_000,
+        retryable: RetryPolicy::is_transient,
+    };
+
+    /// Bus errors, arbitration loss, NACKs and overruns are retried. `Other`
+    /// and unknown kinds are not, they rarely go away by themselves.
+    pub const fn is_transient(kind: ErrorKind) -> bool {
+        matches!(
+            kind,
+            ErrorKind::Bus
+                | ErrorKind::ArbitrationLoss
+                | ErrorKind::NoAcknowledge(_)
+                | ErrorKind::Overrun
+        )
+    }
+
+    /// Delay before retry number `retry`, counted from 1.
+    pub fn backoff_us(&self, retry: u8) -> u32 {
+        let factor = self
+            .backoff_multiplier
+            .checked_pow(retry.saturating_sub(1) as u32)
+            .unwrap_or(u32::MAX);
+        self.initial_backoff_us
+            .saturating_mul(factor)
+            .min(self.max_backoff_us)
+    }
+
+    /// The delay before the next attempt, or `None` to give up after
+    /// `attempt` failed with `kind`, `waited_us` into the transaction.
+    fn next_backoff(&self, attempt: u8, waited_us: u32, kind: ErrorKind) -> Option<u32> {
+        if attempt >= self.max_attempts || !(self.retryable)(kind) {
+            return None;
+        }
+        let backoff_us = self.backoff_us(attempt);
+        (waited_us.saturating_add(backoff_us) <= self.max_total_backoff_us).then_some(backoff_us)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Counters kept by [`RetryI2c`]. They wrap around on overflow.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RetryStats {
+    pub transactions: u32,
+    /// Attempts beyond the first one.
+    pub retries: u32,
+    /// Transactions that succeeded after at least one retry.
+    pub recovered: u32,
+    /// Transactions that failed for good.
+    pub failed: u32,
+    /// Failed reads covering REG09, which were not retried. They count as
+    /// failed too.
+    pub fault_reads_not_retried: u32,
+}
+
+/// An I2C bus that repeats failed transactions according to a
+/// [`RetryPolicy`], waiting on `D` between attempts.
+///
+/// Implements the blocking `I2c` trait for a blocking bus and delay, and
+/// with the `async` feature the async one for an async bus and delay.
+#[derive(Debug)]
+pub struct RetryI2c<I2C, D> {
+    i2c: I2C,
+    delay: D,
+    policy: RetryPolicy,
+    stats: RetryStats,
+}
+
+impl<I2C, D> RetryI2c<I2C, D> {
+    pub fn new(i2c: I2C, delay: D, policy: RetryPolicy) -> Self {
+        RetryI2c {
+            i2c,
+            delay,
+            policy,
+            stats: RetryStats::default(),
+        }
+    }
+
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: RetryPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn stats(&self) -> RetryStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = RetryStats::default();
+    }
+
+    /// Gives back the bus and the delay.
+    pub fn release(self) -> (I2C, D) {
+        (self.i2c, self.delay)
+    }
+}
+
+impl<I2C: ErrorType, D> ErrorType for RetryI2c<I2C, D> {
+    type Error = I2C::Error;
+}
+
+/// What to do after an attempt.
+enum Outcome<E> {
+    Done(Result<(), E>),
+    RetryAfter(u32),
+}
+
+/// The progress of one transaction.
+struct Attempt {
+    number: u8,
+    waited_us: u32,
+    /// `false` for reads covering REG09, see the module documentation.
+    repeatable: bool,
+}
+
+/// Returns `true` if a read of `len` bytes after writing `write` reads
+/// REG09 of the charger.
+fn reads_faults(address: SevenBitAddress, write: &[u8], len: usize) -> bool {
+    let fault = Register::Reg09 as usize;
+    match write.first() {
+        Some(&start) if address == SGM41511_ADDR => {
+            (start as usize) <= fault && fault < start as usize + len
+        }
+        _ => false,
+    }
+}
+
+/// Like [`reads_faults`], for the write followed by reads of a transaction.
+fn transaction_reads_faults(address: SevenBitAddress, operations: &[Operation<'_>]) -> bool {
+    let mut write: &[u8] = &[];
+    let mut len = 0;
+    for operation in operations {
+        match operation {
+            Operation::Write(bytes) if len == 0 => write = bytes,
+            Operation::Write(_) => {}
+            Operation::Read(buffer) => len += buffer.len(),
+        }
+    }
+    reads_faults(address, write, len)
+}
+
+impl<I2C, D> RetryI2c<I2C, D> {
+    /// Counts the start of a transaction and returns its first attempt.
+    fn begin(&mut self, repeatable: bool) -> Attempt {
+        self.stats.transactions = self.stats.transactions.wrapping_add(1);
+        Attempt {
+            number: 1,
+            waited_us: 0,
+            repeatable,
+        }
+    }
+
+    /// Updates the counters after `attempt` ended with `result`, moving on
+    /// to the next attempt if there is one.
+    fn settle<E: embedded_hal::i2c::Error>(
+        &mut self,
+        attempt: &mut Attempt,
+        result: Result<(), E>,
+    ) -> Outcome<E> {
+        let error = match result {
+            Ok(()) => {
+                if attempt.number > 1 {
+                    self.stats.recovered = self.stats.recovered.wrapping_add(1);
+                }
+                return Outcome::Done(Ok(()));
+            }
+            Err(error) => error,
+        };
+        let backoff = if attempt.repeatable {
+            self.policy
+                .next_backoff(attempt.number, attempt.waited_us, error.kind())
+        } else {
+            self.stats.fault_reads_not_retried = self.stats.fault_reads_not_retried.wrapping_add(1);
+            None
+        };
+        match backoff {
+            Some(backoff_us) => {
+                self.stats.retries = self.stats.retries.wrapping_add(1);
+                attempt.number += 1;
+                attempt.waited_us = attempt.waited_us.saturating_add(backoff_us);
+                Outcome::RetryAfter(backoff_us)
+            }
+            None => {
+                self.stats.failed = self.stats.failed.wrapping_add(1);
+                Outcome::Done(Err(error))
+            }
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        I2c(sync = "BlockingI2c", async = "AsyncI2c"),
+        DelayNs(sync = "BlockingDelayNs", async = "AsyncDelayNs")
+    ),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl<I2C, D> I2c for RetryI2c<I2C, D>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    async fn read(&mut self, address: SevenBitAddress, read: &mut [u8]) -> Result<(), Self::Error> {
+        let mut attempt = self.begin(true);
+        loop {
+            let result = self.i2c.read(address, read).await;
+            match self.settle(&mut attempt, result) {
+                Outcome::Done(result) => return result,
+                Outcome::RetryAfter(backoff_us) => self.delay.delay_us(backoff_us).await,
+            }
+        }
+    }
+
+    async fn write(&mut self, address: SevenBitAddress, write: &[u8]) -> Result<(), Self::Error> {
+        let mut attempt = self.begin(true);
+        loop {
+            let result = self.i2c.write(address, write).await;
+            match self.settle(&mut attempt, result) {
+                Outcome::Done(result) => return result,
+                Outcome::RetryAfter(backoff_us) => self.delay.delay_us(backoff_us).await,
+            }
+        }
+    }
+
+    async fn write_read(
+        &mut self,
+        address: SevenBitAddress,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let mut attempt = self.begin(!reads_faults(address, write, read.len()));
+        loop {
+            let result = self.i2c.write_read(address, write, read).await;
+            match self.settle(&mut attempt, result) {
+                Outcome::Done(result) => return result,
+                Outcome::RetryAfter(backoff_us) => self.delay.delay_us(backoff_us).await,
+            }
+        }
+    }
+
+    async fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut attempt = self.begin(!transaction_reads_faults(address, operations));
+        loop {
+            let result = self.i2c.transaction(address, operations).await;
+            match self.settle(&mut attempt, result) {
+                Outcome::Done(result) => return result,
+                Outcome::RetryAfter(backoff_us) => self.delay.delay_us(backoff_us).await,
+            }
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation, SevenBitAddress};
+
+use super::inject::{FaultInjector, Injection};
+use crate::register_map::{RegisterMap, REGISTER_COUNT};
+use crate::{Register, SGM41511_ADDR};
+
+/// Bits the host can write, per register. The rest are read-only.
+const WRITABLE: [u8; REGISTER_COUNT] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x03, 0x80,
+];
+
+/// Self-clearing bits: WD_RST in REG01, IINDET_EN in REG07 and REG_RST in
+/// REG0B. They trigger an action and always read back as 0.
+const WD_RST: u8 = 0x40;
+const IINDET_EN: u8 = 0x80;
+const REG_RST: u8 = 0x80;
+
+/// Register-level model of the SGM41511.
+///
+/// Answers at [`SGM41511_ADDR`] only; other addresses are not acknowledged.
+/// The register pointer auto-increments during bursts and persists between
+/// transactions, like on the chip.
+///
+/// REG09 latches: a fault raised with [`raise_faults`] is returned by the
+/// next read of REG09 even if it was cleared in the meantime, and that read
+/// clears the latch down to the faults still present.
+///
+/// Implements the blocking `I2c` trait, and the async one as well when the
+/// `async` feature is enabled next to `sim`.
+///
+/// [`raise_faults`]: Sgm41511Sim::raise_faults
+#[derive(Clone, Debug)]
+pub struct Sgm41511Sim {
+    registers: [u8; REGISTER_COUNT],
+    pointer: u8,
+    faults_present: u8,
+    faults_latched: u8,
+    watchdog_kicks: u32,
+    transactions: u32,
+    injector: FaultInjector,
+    vbus_lost: Option<(u8, u8)>,
+    vbus_toggles: u32,
+}
+
+impl Default for Sgm41511Sim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sgm41511Sim {
+    /// A chip fresh out of power-on reset.
+    pub fn new() -> Self {
+        Sgm41511Sim {
+            registers: *RegisterMap::RESET.as_raw(),
+            pointer: 0,
+            faults_present: 0,
+            faults_latched: 0,
+            watchdog_kicks: 0,
+            transactions: 0,
+            injector: FaultInjector::default(),
+            vbus_lost: None,
+            vbus_toggles: 0,
+        }
+    }
+
+    /// The registers as the chip holds them, without the side effects of an
+    /// I2C read.
+    pub fn registers(&self) -> RegisterMap {
+        let mut raw = self.registers;
+        raw[Register::Reg09 as usize] = self.faults_latched;
+        RegisterMap::from_raw(raw)
+    }
+
+    /// Number of times WD_RST was set.
+    pub fn watchdog_kicks(&self) -> u32 {
+        self.watchdog_kicks
+    }
+
+    pub fn injector(&self) -> &FaultInjector {
+        &self.injector
+    }
+
+    pub fn injector_mut(&mut self) -> &mut FaultInjector {
+        &mut self.injector
+    }
+
+    /// Replaces the faults injected into later transactions.
+    pub fn set_injector(&mut self, injector: FaultInjector) {
+        self.injector = injector;
+    }
+
+    /// Number of I2C transactions addressed to the chip.
+    pub fn transactions(&self) -> u32 {
+        self.transactions
+    }
+
+    /// Puts REG00 - REG07 and the masks of REG0A back to their power-on
+    /// values, as REG_RST or a power cycle does. Status, faults and the part
+    /// information are kept.
+    pub fn reset_registers(&mut self) {
+        let reset = RegisterMap::RESET.as_raw();
+        self.registers[..=Register::Reg07 as usize]
+            .copy_from_slice(&reset[..=Register::Reg07 as usize]);
+        let reg0a = &mut self.registers[Register::Reg0a as usize];
+        *reg0a = (*reg0a & !WRITABLE[Register::Reg0a as usize]) | reset[Register::Reg0a as usize];
+    }
+
+    /// Lets the I2C watchdog expire: the registers go back to their defaults
+    /// and WATCHDOG_FAULT latches.
+    pub fn expire_watchdog(&mut self) {
+        self.reset_registers();
+        self.faults_latched |= 0x80;
+    }
+
+    /// Sets a register behind the host's back, bypassing the read-only bits
+    /// and the side effects of a write.
+    pub fn poke(&mut self, register: Register, value: u8) {
+        match register {
+            Register::Reg09 => {
+                self.faults_present = value;
+                self.faults_latched = value;
+            }
+            _ => self.registers[register as usize] = value,
+        }
+    }
+
+    /// Sets the read-only status bits of REG08.
+    pub fn set_status(&mut self, reg08: u8) {
+        self.registers[Register::Reg08 as usize] = reg08;
+    }
+
+    /// Sets the read-only status bits of REG0A, keeping the interrupt masks.
+    pub fn set_conditions(&mut self, reg0a: u8) {
+        let mask = WRITABLE[Register::Reg0a as usize];
+        let value = &mut self.registers[Register::Reg0a as usize];
+        *value = (*value & mask) | (reg0a & !mask);
+    }
+
+    /// Makes the fault bits `faults` of REG09 present. They latch.
+    ///
+    /// CHRG_FAULT and NTC_FAULT are codes rather than flags, so the matching
+    /// fields are replaced instead of or-ed.
+    pub fn raise_faults(&mut self, faults: u8) {
+        self.faults_present = merge_faults(self.faults_present, faults);
+        self.faults_latched = merge_faults(self.faults_latched, faults);
+    }
+
+    /// Removes the fault bits `faults` from the present condition. They stay
+    /// in the latch until REG09 is read.
+    pub fn clear_faults(&mut self, faults: u8) {
+        self.faults_present &= !faults;
+    }
+
+    /// Replaces the present faults under `mask` with `faults`, latching the
+    /// new ones.
+    pub(crate) fn set_present_faults(&mut self, mask: u8, faults: u8) {
+        self.faults_present = (self.faults_present & !mask) | (faults & mask);
+        self.faults_latched = merge_faults(self.faults_latched, faults & mask);
+    }
+
+    /// Drops VBUS from the status registers, or brings back the status from
+    /// before the drop. A [`Simulation`](super::Simulation) unplugs and
+    /// replugs its adapter instead.
+    pub fn toggle_vbus(&mut self) {
+        self.vbus_toggles += 1;
+        match self.vbus_lost.take() {
+            Some((reg08, reg0a)) => {
+                self.set_status(reg08);
+                self.set_conditions(reg0a);
+            }
+            None => {
+                self.vbus_lost = Some((
+                    self.registers[Register::Reg08 as usize],
+                    self.registers[Register::Reg0a as usize],
+                ));
+                self.set_status(0);
+                self.set_conditions(0);
+            }
+        }
+    }
+
+    /// Returns the number of VBUS toggles since the last call.
+    pub(crate) fn take_vbus_toggles(&mut self) -> u32 {
+        core::mem::take(&mut self.vbus_toggles)
+    }
+
+    /// Faults present now, what a second read of REG09 returns.
+    pub fn faults_present(&self) -> u8 {
+        self.faults_present
+    }
+
+    fn read_byte(&mut self) -> u8 {
+        let address = self.pointer as usize;
+        self.pointer = self.pointer.saturating_add(1);
+        let value = match address {
+            a if a == Register::Reg09 as usize => {
+                let value = self.faults_latched;
+                self.faults_latched = self.faults_present;
+                value
+            }
+            a if a < REGISTER_COUNT => self.registers[a],
+            _ => 0,
+        };
+        self.injector.read(address as u8, value)
+    }
+
+    fn write_byte(&mut self, value: u8) {
+        let address = self.pointer as usize;
+        self.pointer = self.pointer.saturating_add(1);
+        if address >= REGISTER_COUNT {
+            return;
+        }
+        let mask = WRITABLE[address];
+        let mut value = (self.registers[address] & !mask) | (value & mask);
+        if address == Register::Reg01 as usize && value & WD_RST != 0 {
+            self.watchdog_kicks += 1;
+            value &= !WD_RST;
+        }
+        if address == Register::Reg07 as usize {
+            value &= !IINDET_EN;
+        }
+        if address == Register::Reg0b as usize && value & REG_RST != 0 {
+            self.reset_registers();
+            value &= !REG_RST;
+        }
+        self.registers[address] = value;
+    }
+
+    fn transact(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), ErrorKind> {
+        if address != SGM41511_ADDR {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        self.transactions += 1;
+        let mut corrupt = false;
+        for injection in self.injector.fire(self.transactions) {
+            match injection {
+                Injection::Fail(kind) => return Err(kind),
+                Injection::Corrupt => corrupt = true,
+                Injection::Reset => self.reset_registers(),
+                Injection::LatchFaults(faults) => {
+                    self.faults_latched = merge_faults(self.faults_latched, faults)
+                }
+                Injection::ToggleVbus => self.toggle_vbus(),
+            }
+        }
+        for operation in operations.iter_mut() {
+            match operation {
+                Operation::Write(bytes) => {
+                    if let Some((pointer, data)) = bytes.split_first() {
+                        self.pointer = *pointer;
+                        for value in data {
+                            self.write_byte(*value);
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for value in buffer.iter_mut() {
+                        *value = self.read_byte();
+                    }
+                }
+            }
+        }
+        if corrupt {
+            self.corrupt(operations);
+        }
+        Ok(())
+    }
+}
+
+impl Sgm41511Sim {
+    /// Flips one random bit of the bytes read in `operations`.
+    fn corrupt(&mut self, operations: &mut [Operation<'_>]) {
+        let read = |operation: &Operation<'_>| match operation {
+            Operation::Read(buffer) => buffer.len() as u32,
+            Operation::Write(_) => 0,
+        };
+        let total: u32 = operations.iter().map(read).sum();
+        if total == 0 {
+            return;
+        }
+        let rng = self.injector.rng();
+        let mut index = rng.below(total);
+        let bit = rng.below(8);
+        for operation in operations {
+            if let Operation::Read(buffer) = operation {
+                if let Some(value) = buffer.get_mut(index as usize) {
+                    *value ^= 1 << bit;
+                    return;
+                }
+                index -= buffer.len() as u32;
+            }
+        }
+    }
+}
+
+/// Merges fault bits into a REG09 value, replacing the CHRG_FAULT and
+/// NTC_FAULT codes when `faults` sets them.
+fn merge_faults(current: u8, faults: u8) -> u8 {
+    let mut value = current | (faults & 0xc8);
+    if faults & 0x30 != 0 {
+        value = (value & !0x30) | (faults & 0x30);
+    }
+    if faults & 0x07 != 0 {
+        value = (value & !0x07) | (faults & 0x07);
+    }
+    value
+}
+
+impl ErrorType for Sgm41511Sim {
+    type Error = ErrorKind;
+}
+
+impl embedded_hal::i2c::I2c for Sgm41511Sim {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transact(address, operations)
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for Sgm41511Sim {
+    async fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transact(address, operations)
+    }
+}
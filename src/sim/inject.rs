@@ -0,0 +1,155 @@
+use std::vec::Vec;
+
+use embedded_hal::i2c::ErrorKind;
+
+use crate::Register;
+
+/// Small xorshift generator, so injected faults reproduce from a seed
+/// without pulling in an RNG crate.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct XorShift32(u32);
+
+impl XorShift32 {
+    /// A zero seed is replaced by a fixed non-zero one, xorshift never
+    /// leaves zero.
+    pub const fn new(seed: u32) -> Self {
+        XorShift32(if seed == 0 { 0x9e37_79b9 } else { seed })
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.0 = x;
+        x
+    }
+
+    /// Uniform in `0..bound`, `bound` must not be zero.
+    pub fn below(&mut self, bound: u32) -> u32 {
+        self.next_u32() % bound
+    }
+}
+
+/// When an [`Injection`] fires. Transactions are counted from 1 across the
+/// whole life of the simulated chip, failed ones included.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Trigger {
+    /// On the nth transaction only.
+    Transaction(u32),
+    /// On every nth transaction.
+    Every(u32),
+    /// On each transaction with a probability of `per_mille` / 1000.
+    Random { per_mille: u16 },
+}
+
+/// A fault applied to a transaction.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Injection {
+    /// The transaction fails with `ErrorKind`, nothing is read or written.
+    /// Use `NoAcknowledge` for NACKs and `ArbitrationLoss` for a lost bus.
+    Fail(ErrorKind),
+    /// One random bit of the bytes read in the transaction is flipped.
+    Corrupt,
+    /// The chip resets its registers to their defaults before the
+    /// transaction, as on a brownout.
+    Reset,
+    /// The REG09 fault bits latch before the transaction, without being
+    /// present: they show up on the next read of REG09 only.
+    LatchFaults(u8),
+    /// VBUS is lost, or restored if it was lost before.
+    ToggleVbus,
+}
+
+/// Faults to inject into a [`Sgm41511Sim`](super::Sgm41511Sim).
+///
+/// ```rust
+/// use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+/// use sgm41511::sim::{FaultInjector, Injection, Sgm41511Sim, Trigger};
+/// use sgm41511::{Error, Register, SGM41511};
+/// let mut injector = FaultInjector::new(1);
+/// injector.inject(
+///     Trigger::Transaction(2),
+///     Injection::Fail(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data)),
+/// );
+/// let mut sim = Sgm41511Sim::new();
+/// sim.set_injector(injector);
+/// let mut device = SGM41511::new(&mut sim);
+/// assert!(device.read_register(Register::Reg00).is_ok());
+/// assert!(matches!(device.read_register(Register::Reg00), Err(Error::Bus(_))));
+/// assert!(device.read_register(Register::Reg00).is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct FaultInjector {
+    rng: XorShift32,
+    rules: Vec<(Trigger, Injection)>,
+    stuck: Vec<(Register, u8, u8)>,
+    injected: u32,
+}
+
+impl Default for FaultInjector {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl FaultInjector {
+    /// No faults, with the random triggers seeded by `seed`.
+    pub fn new(seed: u32) -> Self {
+        FaultInjector {
+            rng: XorShift32::new(seed),
+            rules: Vec::new(),
+            stuck: Vec::new(),
+            injected: 0,
+        }
+    }
+
+    pub fn inject(&mut self, trigger: Trigger, injection: Injection) {
+        self.rules.push((trigger, injection));
+    }
+
+    /// Forces the bits `mask` of `register` to `value` in every read.
+    pub fn stick_bits(&mut self, register: Register, mask: u8, value: u8) {
+        self.stuck.push((register, mask, value & mask));
+    }
+
+    /// Removes every rule and stuck bit. The RNG keeps its state.
+    pub fn clear(&mut self) {
+        self.rules.clear();
+        self.stuck.clear();
+    }
+
+    /// Number of injections fired so far.
+    pub fn injected(&self) -> u32 {
+        self.injected
+    }
+
+    pub(crate) fn rng(&mut self) -> &mut XorShift32 {
+        &mut self.rng
+    }
+
+    /// The injections firing on transaction number `transaction`.
+    pub(crate) fn fire(&mut self, transaction: u32) -> Vec<Injection> {
+        let mut fired = Vec::new();
+        for (trigger, injection) in &self.rules {
+            let hit = match *trigger {
+                Trigger::Transaction(n) => transaction == n,
+                Trigger::Every(n) => n != 0 && transaction % n == 0,
+                Trigger::Random { per_mille } => self.rng.below(1000) < per_mille as u32,
+            };
+            if hit {
+                fired.push(*injection);
+            }
+        }
+        self.injected += fired.len() as u32;
+        fired
+    }
+
+    /// Applies the stuck bits to a byte read from `address`.
+    pub(crate) fn read(&self, address: u8, value: u8) -> u8 {
+        self.stuck
+            .iter()
+            .filter(|(register, _, _)| *register as u8 == address)
+            .fold(value, |value, (_, mask, stuck)| (value & !mask) | stuck)
+    }
+}
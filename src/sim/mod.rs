@@ -0,0 +1,34 @@
+//! Simulated SGM41511 for host tests.
+//!
+//! [`Sgm41511Sim`] is an I2C target implementing both the blocking and the
+//! async `I2c` traits, so the driver runs against it unchanged.
+//! [`Simulation`] adds a time-stepped model of the adapter, the battery and
+//! the charge cycle on top of the registers, so whole charge sessions run in
+//! milliseconds. A [`FaultInjector`] makes either of them misbehave on
+//! purpose, and a [`Scenario`] scripts a whole session from a small TOML
+//! file.
+//!
+//! ```rust
+//! use sgm41511::sim::Sgm41511Sim;
+//! use sgm41511::types::*;
+//! use sgm41511::SGM41511;
+//! let mut sim = Sgm41511Sim::new();
+//! let mut device = SGM41511::new(&mut sim);
+//! assert_eq!(device.identify().unwrap().part_number, PartNumber::SGM41511);
+//! device.set_charge_current_ma(1000).unwrap();
+//! assert_eq!(sim.registers().reg02().unwrap().charge_current, ChargeCurrent::_960mA);
+//! ```
+
+mod device;
+mod inject;
+mod physics;
+mod scenario;
+mod simulation;
+mod toml;
+
+pub use device::Sgm41511Sim;
+pub use inject::{FaultInjector, Injection, Trigger, XorShift32};
+pub use physics::{Adapter, Battery, TemperatureZone};
+pub use scenario::{Action, Expectation, Expected, Failure, Report, Scenario, TimedAction};
+pub use simulation::{Simulation, DEFAULT_STEP_MS};
+pub use toml::ParseError;
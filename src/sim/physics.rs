@@ -0,0 +1,153 @@
+use crate::types::{InputCurrentLimit, VBUSStatus};
+
+/// A supply plugged into VBUS.
+///
+/// The output sags by `resistance_mohm` under load, and collapses when more
+/// than `current_limit_ma` is drawn, which the charger sees as VINDPM.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Adapter {
+    /// Open-circuit output voltage.
+    pub voltage_mv: u16,
+    pub current_limit_ma: u16,
+    /// Output impedance, including the cable.
+    pub resistance_mohm: u16,
+    /// What input source detection reports in REG08.
+    pub kind: VBUSStatus,
+}
+
+impl Adapter {
+    /// A dedicated charging port: 5 V, shorted D+/D-.
+    pub const fn dcp(current_limit_ma: u16) -> Self {
+        Adapter {
+            voltage_mv: 5000,
+            current_limit_ma,
+            resistance_mohm: 150,
+            kind: VBUSStatus::USBDCP,
+        }
+    }
+
+    /// A USB host port, limited to 500 mA.
+    pub const fn sdp() -> Self {
+        Adapter {
+            voltage_mv: 5000,
+            current_limit_ma: 500,
+            resistance_mohm: 250,
+            kind: VBUSStatus::USBHostSDP,
+        }
+    }
+
+    /// Output voltage while `current_ma` is drawn.
+    pub fn voltage_at(&self, current_ma: f32) -> f32 {
+        self.voltage_mv as f32 - current_ma * self.resistance_mohm as f32 / 1000.0
+    }
+
+    /// Input current limit the SGM41511 picks after detecting this source,
+    /// the same one the driver recommends for it.
+    pub(crate) fn detected_limit(&self) -> InputCurrentLimit {
+        self.kind
+            .recommended_input_current_limit()
+            .unwrap_or(InputCurrentLimit::_500mA)
+    }
+}
+
+/// Open-circuit voltage of a typical Li-ion cell, in mV, at 0 %, 5 %,
+/// 10 %, 20 % ... 100 % state of charge.
+const OCV_CURVE: [(f32, f32); 12] = [
+    (0.00, 2800.0),
+    (0.05, 3450.0),
+    (0.10, 3600.0),
+    (0.20, 3690.0),
+    (0.30, 3740.0),
+    (0.40, 3780.0),
+    (0.50, 3820.0),
+    (0.60, 3880.0),
+    (0.70, 3950.0),
+    (0.80, 4030.0),
+    (0.90, 4110.0),
+    (1.00, 4200.0),
+];
+
+/// A single Li-ion cell.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Battery {
+    pub capacity_mah: u16,
+    /// Internal resistance, seen as the voltage step between the open-circuit
+    /// and the terminal voltage.
+    pub resistance_mohm: u16,
+    /// State of charge, 0.0 - 1.0. It may go slightly above 1.0 when the cell
+    /// is held at a charge voltage above 4.2 V.
+    pub soc: f32,
+}
+
+impl Battery {
+    pub const fn new(capacity_mah: u16, soc: f32) -> Self {
+        Battery {
+            capacity_mah,
+            resistance_mohm: 100,
+            soc,
+        }
+    }
+
+    /// Open-circuit voltage in mV, interpolated from a Li-ion OCV curve.
+    /// Above 100 % the curve continues with its last slope.
+    pub fn ocv_mv(&self) -> f32 {
+        let soc = self.soc.max(0.0);
+        let index = OCV_CURVE
+            .iter()
+            .position(|(point, _)| *point >= soc)
+            .unwrap_or(OCV_CURVE.len() - 1)
+            .max(1);
+        let (soc0, mv0) = OCV_CURVE[index - 1];
+        let (soc1, mv1) = OCV_CURVE[index];
+        mv0 + (soc - soc0) * (mv1 - mv0) / (soc1 - soc0)
+    }
+
+    /// Terminal voltage in mV while `current_ma` flows in (positive) or
+    /// out (negative).
+    pub fn voltage_at(&self, current_ma: f32) -> f32 {
+        self.ocv_mv() + current_ma * self.resistance_mohm as f32 / 1000.0
+    }
+
+    /// Moves `current_ma` in (positive) or out (negative) for `dt_ms`.
+    pub(crate) fn transfer(&mut self, current_ma: f32, dt_ms: u32) {
+        let mah = current_ma * dt_ms as f32 / 3_600_000.0;
+        self.soc = (self.soc + mah / self.capacity_mah as f32).max(0.0);
+    }
+}
+
+/// JEITA temperature zone of the battery, as the charger reads it on TS.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TemperatureZone {
+    /// Below 0 °C, charging suspended.
+    Cold,
+    /// 0 - 10 °C, charge current reduced per JEITA_ISET.
+    Cool,
+    Normal,
+    /// 45 - 60 °C, charge voltage reduced per JEITA_VSET.
+    Warm,
+    /// Above 60 °C, charging suspended.
+    Hot,
+}
+
+impl TemperatureZone {
+    pub fn from_celsius(celsius: f32) -> Self {
+        match celsius {
+            t if t < 0.0 => TemperatureZone::Cold,
+            t if t < 10.0 => TemperatureZone::Cool,
+            t if t <= 45.0 => TemperatureZone::Normal,
+            t if t <= 60.0 => TemperatureZone::Warm,
+            _ => TemperatureZone::Hot,
+        }
+    }
+
+    /// NTC_FAULT code reported in REG09.
+    pub(crate) const fn ntc_fault(self) -> u8 {
+        match self {
+            TemperatureZone::Cold => 0x05,
+            TemperatureZone::Cool => 0x03,
+            TemperatureZone::Normal => 0x00,
+            TemperatureZone::Warm => 0x02,
+            TemperatureZone::Hot => 0x06,
+        }
+    }
+}
@@ -0,0 +1,613 @@
+use std::fmt;
+use std::format;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use super::physics::{Adapter, Battery};
+use super::simulation::{Simulation, DEFAULT_STEP_MS};
+use super::toml::{self, Entry, ParseError, Table, Value};
+use crate::register_map::RegisterMap;
+use crate::types::*;
+use crate::Register;
+
+/// Something that happens to the simulation at a point in time.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Action {
+    Plug(Adapter),
+    Unplug,
+    SetTemperature(f32),
+    SetSystemLoad(u16),
+    /// The chip resets its registers, as on a brownout.
+    ResetRegisters,
+    ExpireWatchdog,
+    /// REG09 bits, see [`Sgm41511Sim::raise_faults`](super::Sgm41511Sim::raise_faults).
+    RaiseFaults(u8),
+    ClearFaults(u8),
+}
+
+/// An [`Action`] on the timeline.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct TimedAction {
+    pub at_ms: u64,
+    pub action: Action,
+    /// Line of the `[[event]]` table in the scenario file.
+    pub line: usize,
+}
+
+/// The value an [`Expectation`] compares a register field with.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Expected {
+    /// Name of an enum variant, as printed by `Debug`.
+    Name(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Name(name) => f.write_str(name),
+            Expected::Integer(value) => write!(f, "{:#04x}", value),
+            Expected::Boolean(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A check of a decoded register field at a point in time.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Expectation {
+    pub at_ms: u64,
+    /// `reg08.charge_status` for a field, or `reg02` for the raw register.
+    pub field: String,
+    pub equals: Expected,
+    /// Line of the `[[expect]]` table in the scenario file.
+    pub line: usize,
+}
+
+/// A charge session described as a timeline of actions and expectations.
+///
+/// Scenario files use a subset of TOML: top-level settings, followed by
+/// `[[event]]` and `[[expect]]` tables. Times are milliseconds, or strings
+/// such as `"500ms"`, `"30s"`, `"2h"` or `"1h30min"`.
+///
+/// ```toml
+/// name = "DCP with a warm battery"
+/// capacity_mah = 1000     # battery, default 1000
+/// soc = 0.2               # state of charge, default 0.5
+/// temperature_c = 25      # default 25
+/// step = "1s"             # simulation step, default 1 s
+/// policy_interval = "10s" # how often the policy runs, default 1 s
+/// duration = "3h"         # run at least this long, default 0
+///
+/// [[event]]
+/// at = 0
+/// action = "plug"         # plug, unplug, temperature, load, reset,
+///                         # expire_watchdog, raise_faults, clear_faults
+/// adapter = "dcp"         # sdp, cdp, dcp, unknown or non_standard
+/// voltage_mv = 5000       # optional overrides
+/// current_limit_ma = 2000
+///
+/// [[event]]
+/// at = "30min"
+/// action = "temperature"
+/// celsius = 50
+///
+/// [[expect]]
+/// at = "31min"
+/// field = "reg09.ntc_fault"
+/// equals = "Warm"
+/// ```
+///
+/// The other actions take `milliamps` (load) or `bits` (raise_faults,
+/// clear_faults). Expectations compare a field of the decoded register
+/// with the name of an enum variant, a boolean or an integer, or a whole
+/// register with its raw value.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Scenario {
+    pub name: String,
+    pub battery: Battery,
+    pub temperature_c: f32,
+    pub step_ms: u32,
+    pub policy_interval_ms: u64,
+    pub duration_ms: u64,
+    pub actions: Vec<TimedAction>,
+    pub expectations: Vec<Expectation>,
+}
+
+/// A scenario that ran to its end.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Report {
+    /// Number of expectations met.
+    pub checked: usize,
+    pub duration_ms: u64,
+}
+
+/// The first expectation of a scenario that was not met.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Failure {
+    /// Simulated time of the check since the start of the run. Later than
+    /// the expectation's `at` if the policy ran past it.
+    pub at_ms: u64,
+    pub line: usize,
+    pub field: String,
+    pub expected: Expected,
+    /// The value found, as printed by `Debug`.
+    pub actual: String,
+}
+
+/// Formats the failure as `0:31:00.000 (line 24): reg09.ntc_fault is
+/// Normal, expected Warm`.
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (hours, rest) = (self.at_ms / 3_600_000, self.at_ms % 3_600_000);
+        write!(
+            f,
+            "{}:{:02}:{:02}.{:03} (line {}): {} is {}, expected {}",
+            hours,
+            rest / 60_000,
+            rest % 60_000 / 1000,
+            rest % 1000,
+            self.line,
+            self.field,
+            self.actual,
+            self.expected
+        )
+    }
+}
+
+impl Scenario {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut tables = toml::parse(text)?.into_iter();
+        let mut settings = Fields::new(tables.next().expect("parse returns the top-level table"));
+        let policy_line = settings.line("policy_interval");
+        let mut scenario = Scenario {
+            name: settings.string("name")?.unwrap_or_default(),
+            battery: Battery::new(
+                settings
+                    .integer("capacity_mah", 1, u16::MAX as i64)?
+                    .unwrap_or(1000) as u16,
+                settings.float("soc")?.unwrap_or(0.5),
+            ),
+            temperature_c: settings.float("temperature_c")?.unwrap_or(25.0),
+            step_ms: settings
+                .duration("step")?
+                .map_or(DEFAULT_STEP_MS, |ms| ms.clamp(1, u32::MAX as u64) as u32),
+            policy_interval_ms: settings.duration("policy_interval")?.unwrap_or(1000),
+            duration_ms: settings.duration("duration")?.unwrap_or(0),
+            actions: Vec::new(),
+            expectations: Vec::new(),
+        };
+        if let Some(resistance) = settings.integer("resistance_mohm", 0, u16::MAX as i64)? {
+            scenario.battery.resistance_mohm = resistance as u16;
+        }
+        if scenario.policy_interval_ms == 0 {
+            return Err(ParseError::new(
+                policy_line,
+                "`policy_interval` must not be 0",
+            ));
+        }
+        settings.finish()?;
+
+        for table in tables {
+            let line = table.line;
+            let mut fields = Fields::new(table);
+            let at_ms = fields
+                .duration("at")?
+                .ok_or_else(|| ParseError::new(line, "missing `at`"))?;
+            match fields.table.name.clone().as_str() {
+                "event" => {
+                    let action = parse_action(&mut fields)?;
+                    scenario.actions.push(TimedAction {
+                        at_ms,
+                        action,
+                        line,
+                    });
+                }
+                "expect" => {
+                    let expectation = parse_expectation(&mut fields, at_ms)?;
+                    scenario.expectations.push(expectation);
+                }
+                name => {
+                    return Err(ParseError::new(line, format!("unknown table `{}`", name)));
+                }
+            }
+            fields.finish()?;
+        }
+        Ok(scenario)
+    }
+
+    /// The simulation the scenario starts from.
+    pub fn simulation(&self) -> Simulation {
+        let mut sim = Simulation::new(self.battery);
+        sim.set_temperature_c(self.temperature_c);
+        sim.set_step_ms(self.step_ms);
+        sim
+    }
+
+    /// Runs the scenario on a fresh [`simulation`](Scenario::simulation).
+    pub fn run(&self, policy: impl FnMut(&mut Simulation)) -> Result<Report, Failure> {
+        self.run_on(&mut self.simulation(), policy)
+    }
+
+    /// Runs the scenario on `sim`, for example one with a fault injector.
+    ///
+    /// At each point in time the actions take place first, in file order.
+    /// Then `policy` runs if it is due, every `policy_interval` from the
+    /// start, and finally the expectations are checked. The run stops at the
+    /// first expectation not met.
+    ///
+    /// `policy` may advance the simulation itself. If it runs past the next
+    /// point in time, that point is handled late, at the time the policy
+    /// left the simulation at, and a [`Failure`] reports that time.
+    pub fn run_on(
+        &self,
+        sim: &mut Simulation,
+        mut policy: impl FnMut(&mut Simulation),
+    ) -> Result<Report, Failure> {
+        let start = sim.now_ms();
+        let end = self
+            .actions
+            .iter()
+            .map(|action| action.at_ms)
+            .chain(
+                self.expectations
+                    .iter()
+                    .map(|expectation| expectation.at_ms),
+            )
+            .fold(self.duration_ms, u64::max);
+        let mut next_policy = 0;
+        let mut checked = 0;
+        let mut now = 0;
+        loop {
+            for timed in self.actions.iter().filter(|timed| timed.at_ms == now) {
+                apply(sim, timed.action);
+            }
+            if now == next_policy {
+                policy(sim);
+                next_policy += self.policy_interval_ms;
+            }
+            let map = sim.chip().registers();
+            for expectation in self.expectations.iter().filter(|e| e.at_ms == now) {
+                let actual =
+                    field_value(&map, &expectation.field).expect("fields are checked when parsing");
+                if !matches(&expectation.equals, &actual) {
+                    return Err(Failure {
+                        at_ms: sim.now_ms() - start,
+                        line: expectation.line,
+                        field: expectation.field.clone(),
+                        expected: expectation.equals.clone(),
+                        actual,
+                    });
+                }
+                checked += 1;
+            }
+            if now >= end {
+                break;
+            }
+            let next = self
+                .actions
+                .iter()
+                .map(|action| action.at_ms)
+                .chain(
+                    self.expectations
+                        .iter()
+                        .map(|expectation| expectation.at_ms),
+                )
+                .filter(|at| *at > now)
+                .fold(end.min(next_policy), u64::min);
+            sim.advance((start + next).saturating_sub(sim.now_ms()));
+            now = next;
+        }
+        Ok(Report {
+            checked,
+            duration_ms: end,
+        })
+    }
+}
+
+fn apply(sim: &mut Simulation, action: Action) {
+    match action {
+        Action::Plug(adapter) => sim.plug(adapter),
+        Action::Unplug => sim.unplug(),
+        Action::SetTemperature(celsius) => sim.set_temperature_c(celsius),
+        Action::SetSystemLoad(milliamps) => sim.set_system_load_ma(milliamps),
+        Action::ResetRegisters => sim.chip_mut().reset_registers(),
+        Action::ExpireWatchdog => sim.chip_mut().expire_watchdog(),
+        Action::RaiseFaults(bits) => sim.chip_mut().raise_faults(bits),
+        Action::ClearFaults(bits) => sim.chip_mut().clear_faults(bits),
+    }
+}
+
+fn parse_action(fields: &mut Fields) -> Result<Action, ParseError> {
+    let line = fields.table.line;
+    let action = fields
+        .string("action")?
+        .ok_or_else(|| ParseError::new(line, "missing `action`"))?;
+    let action = match action.as_str() {
+        "plug" => {
+            let mut adapter = match fields.string("adapter")?.as_deref() {
+                None | Some("dcp") => Adapter::dcp(2400),
+                Some("sdp") => Adapter::sdp(),
+                Some(kind) => {
+                    let kind = match kind {
+                        "cdp" => VBUSStatus::USBCDP,
+                        "unknown" => VBUSStatus::UnknownAdapter,
+                        "non_standard" => VBUSStatus::NonStandardAdapter,
+                        _ => return Err(ParseError::new(line, "unknown adapter")),
+                    };
+                    Adapter {
+                        kind,
+                        ..Adapter::dcp(2400)
+                    }
+                }
+            };
+            if let Some(voltage) = fields.integer("voltage_mv", 0, u16::MAX as i64)? {
+                adapter.voltage_mv = voltage as u16;
+            }
+            if let Some(limit) = fields.integer("current_limit_ma", 0, u16::MAX as i64)? {
+                adapter.current_limit_ma = limit as u16;
+            }
+            if let Some(resistance) = fields.integer("resistance_mohm", 0, u16::MAX as i64)? {
+                adapter.resistance_mohm = resistance as u16;
+            }
+            Action::Plug(adapter)
+        }
+        "unplug" => Action::Unplug,
+        "temperature" => Action::SetTemperature(
+            fields
+                .float("celsius")?
+                .ok_or_else(|| ParseError::new(line, "missing `celsius`"))?,
+        ),
+        "load" => Action::SetSystemLoad(
+            fields
+                .integer("milliamps", 0, u16::MAX as i64)?
+                .ok_or_else(|| ParseError::new(line, "missing `milliamps`"))? as u16,
+        ),
+        "reset" => Action::ResetRegisters,
+        "expire_watchdog" => Action::ExpireWatchdog,
+        "raise_faults" | "clear_faults" => {
+            let bits = fields
+                .integer("bits", 0, u8::MAX as i64)?
+                .ok_or_else(|| ParseError::new(line, "missing `bits`"))?
+                as u8;
+            if action == "raise_faults" {
+                Action::RaiseFaults(bits)
+            } else {
+                Action::ClearFaults(bits)
+            }
+        }
+        _ => return Err(ParseError::new(line, "unknown action")),
+    };
+    Ok(action)
+}
+
+fn parse_expectation(fields: &mut Fields, at_ms: u64) -> Result<Expectation, ParseError> {
+    let line = fields.table.line;
+    let field = fields
+        .string("field")?
+        .ok_or_else(|| ParseError::new(line, "missing `field`"))?;
+    if field_value(&RegisterMap::RESET, &field).is_none() {
+        return Err(ParseError::new(line, format!("unknown field `{}`", field)));
+    }
+    let entry = fields
+        .take("equals")
+        .ok_or_else(|| ParseError::new(line, "missing `equals`"))?;
+    let equals = match &entry.value {
+        Value::String(name) => Expected::Name(name.clone()),
+        Value::Integer(value) => Expected::Integer(*value),
+        Value::Boolean(value) => Expected::Boolean(*value),
+        Value::Float(_) => return Err(ParseError::new(entry.line, "`equals` cannot be a float")),
+    };
+    Ok(Expectation {
+        at_ms,
+        field,
+        equals,
+        line,
+    })
+}
+
+/// The value of `field` in `map`: a `Debug` printed field such as
+/// `reg08.charge_status`, or the raw value of a register such as `reg02`.
+fn field_value(map: &RegisterMap, field: &str) -> Option<String> {
+    let (register, name) = match field.split_once('.') {
+        Some((register, name)) => (register, Some(name)),
+        None => (field, None),
+    };
+    let register = match register {
+        "reg00" => Register::Reg00,
+        "reg01" => Register::Reg01,
+        "reg02" => Register::Reg02,
+        "reg03" => Register::Reg03,
+        "reg04" => Register::Reg04,
+        "reg05" => Register::Reg05,
+        "reg06" => Register::Reg06,
+        "reg07" => Register::Reg07,
+        "reg08" => Register::Reg08,
+        "reg09" => Register::Reg09,
+        "reg0a" => Register::Reg0a,
+        "reg0b" => Register::Reg0b,
+        _ => return None,
+    };
+    let Some(name) = name else {
+        return Some(format!("{:#04x}", map.raw(register)));
+    };
+    let decoded = match register {
+        Register::Reg00 => format!("{:?}", map.lenient::<Reg00Values>(register).value),
+        Register::Reg01 => format!("{:?}", map.lenient::<Reg01Values>(register).value),
+        Register::Reg02 => format!("{:?}", map.lenient::<Reg02Values>(register).value),
+        Register::Reg03 => format!("{:?}", map.lenient::<Reg03Values>(register).value),
+        Register::Reg04 => format!("{:?}", map.lenient::<Reg04Values>(register).value),
+        Register::Reg05 => format!("{:?}", map.lenient::<Reg05Values>(register).value),
+        Register::Reg06 => format!("{:?}", map.lenient::<Reg06Values>(register).value),
+        Register::Reg07 => format!("{:?}", map.lenient::<Reg07Values>(register).value),
+        Register::Reg08 => format!("{:?}", map.lenient::<Reg08Values>(register).value),
+        Register::Reg09 => format!("{:?}", map.lenient::<Reg09Values>(register).value),
+        Register::Reg0a => format!("{:?}", map.lenient::<Reg0aValues>(register).value),
+        Register::Reg0b => format!("{:?}", map.lenient::<Reg0bValues>(register).value),
+    };
+    // `RegXXValues { name: Value, ... }`, every field value is a single token.
+    let key = format!(" {}: ", name);
+    let start = decoded.find(&key)? + key.len();
+    let value = decoded[start..].split([',', ' ']).next()?;
+    Some(value.to_string())
+}
+
+fn matches(expected: &Expected, actual: &str) -> bool {
+    match expected {
+        Expected::Name(name) => name == actual,
+        Expected::Boolean(value) => actual.parse() == Ok(*value),
+        Expected::Integer(value) => {
+            let parsed = match actual.strip_prefix("0x") {
+                Some(hex) => i64::from_str_radix(hex, 16).ok(),
+                None => actual.parse().ok(),
+            };
+            parsed == Some(*value)
+        }
+    }
+}
+
+/// The entries of a table, taken one by one so leftovers can be reported.
+struct Fields {
+    table: Table,
+}
+
+impl Fields {
+    fn new(table: Table) -> Self {
+        Fields { table }
+    }
+
+    /// Line of `key`, or of the table header if it is missing.
+    fn line(&self, key: &str) -> usize {
+        self.table
+            .entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map_or(self.table.line, |entry| entry.line)
+    }
+
+    fn take(&mut self, key: &str) -> Option<Entry> {
+        let index = self
+            .table
+            .entries
+            .iter()
+            .position(|entry| entry.key == key)?;
+        Some(self.table.entries.remove(index))
+    }
+
+    fn string(&mut self, key: &str) -> Result<Option<String>, ParseError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Entry {
+                value: Value::String(value),
+                ..
+            }) => Ok(Some(value)),
+            Some(entry) => Err(mismatch(&entry, "string")),
+        }
+    }
+
+    fn integer(&mut self, key: &str, min: i64, max: i64) -> Result<Option<i64>, ParseError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Entry {
+                value: Value::Integer(value),
+                ..
+            }) if (min..=max).contains(&value) => Ok(Some(value)),
+            Some(Entry {
+                value: Value::Integer(_),
+                line,
+                ..
+            }) => Err(ParseError::new(
+                line,
+                format!("`{}` out of range {} - {}", key, min, max),
+            )),
+            Some(entry) => Err(mismatch(&entry, "integer")),
+        }
+    }
+
+    fn float(&mut self, key: &str) -> Result<Option<f32>, ParseError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Entry {
+                value: Value::Float(value),
+                ..
+            }) => Ok(Some(value as f32)),
+            Some(Entry {
+                value: Value::Integer(value),
+                ..
+            }) => Ok(Some(value as f32)),
+            Some(entry) => Err(mismatch(&entry, "number")),
+        }
+    }
+
+    /// Milliseconds, or a string such as `"1h30min"`.
+    fn duration(&mut self, key: &str) -> Result<Option<u64>, ParseError> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Entry {
+                value: Value::Integer(value),
+                line,
+                ..
+            }) => u64::try_from(value)
+                .map(Some)
+                .map_err(|_| ParseError::new(line, "negative duration")),
+            Some(Entry {
+                value: Value::String(text),
+                line,
+                ..
+            }) => parse_duration(&text)
+                .map(Some)
+                .ok_or_else(|| ParseError::new(line, format!("invalid duration `{}`", text))),
+            Some(entry) => Err(mismatch(&entry, "duration")),
+        }
+    }
+
+    /// Fails on the first entry nobody asked for.
+    fn finish(self) -> Result<(), ParseError> {
+        match self.table.entries.first() {
+            Some(entry) => Err(ParseError::new(
+                entry.line,
+                format!("unknown key `{}`", entry.key),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+fn mismatch(entry: &Entry, expected: &str) -> ParseError {
+    ParseError::new(
+        entry.line,
+        format!(
+            "`{}` must be a {}, not a {}",
+            entry.key,
+            expected,
+            entry.value.type_name()
+        ),
+    )
+}
+
+/// Parses `"500ms"`, `"30s"`, `"2h"` or `"1h30min"` into milliseconds.
+fn parse_duration(text: &str) -> Option<u64> {
+    let mut rest = text.trim();
+    let mut total: u64 = 0;
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let value: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let scale = match &rest[..unit] {
+            "ms" => 1,
+            "s" => 1000,
+            "min" => 60_000,
+            "h" => 3_600_000,
+            _ => return None,
+        };
+        total = total.checked_add(value.checked_mul(scale)?)?;
+        rest = &rest[unit..];
+    }
+    Some(total)
+}
@@ -0,0 +1,585 @@
+use embedded_hal::i2c::{ErrorKind, ErrorType, Operation, SevenBitAddress};
+
+use super::physics::{Adapter, Battery, TemperatureZone};
+use super::Sgm41511Sim;
+use crate::register_map::RegisterMap;
+use crate::types::*;
+use crate::Register;
+
+/// Default length of a simulation step.
+pub const DEFAULT_STEP_MS: u32 = 1000;
+
+/// Conversion efficiency of the buck converter.
+const EFFICIENCY: f32 = 0.9;
+/// VBUS below this is not a valid input.
+const VBUS_UVLO_MV: u16 = 3900;
+/// Below this battery voltage the charger pre-charges.
+const BATLOWV_MV: f32 = 3000.0;
+/// JEITA_VSET limit in the warm zone.
+const JEITA_WARM_VREG_MV: f32 = 4100.0;
+
+/// REG09 bits driven by the model: CHRG_FAULT, BAT_FAULT and NTC_FAULT.
+const MODEL_FAULTS: u8 = 0x3f;
+
+/// Where the charger is in its charge cycle.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Cycle {
+    /// No valid input, or charging disabled.
+    Idle,
+    /// Pre-charge, constant current or constant voltage.
+    Charging,
+    /// Termination reached, the top-off timer keeps charging. Reported as
+    /// charge termination done with TOPOFF_ACTIVE set.
+    TopOff { remaining_ms: u32 },
+    /// Charge done, waiting for the battery to drop below the recharge
+    /// threshold.
+    Done,
+    /// The safety timer expired. Toggling CHG_CONFIG or the input restarts
+    /// the cycle.
+    TimerExpired,
+}
+
+/// A [`Sgm41511Sim`] wired to an adapter, a Li-ion cell and an NTC, run on a
+/// virtual clock.
+///
+/// The driver talks to the simulation like to the bare chip. Time only
+/// passes in [`advance`](Simulation::advance), which steps the model in fixed
+/// increments and updates REG08, REG09 and REG0A from it:
+///
+/// - pre-charge below 3.0 V, then constant current and constant voltage,
+/// - termination at ITERM, the top-off timer and recharge below
+///   VREG - VRECHG,
+/// - the fast charge safety timer, doubled during DPM and JEITA cool when
+///   TMR2X_EN is set,
+/// - IINDPM, and VINDPM when the adapter sags or hits its current limit,
+/// - the JEITA zones of the NTC, input over-voltage and battery
+///   over-voltage,
+/// - the I2C watchdog: without a WD_RST in time, the registers fall back to
+///   their defaults.
+///
+/// Thermal regulation is not modelled.
+///
+/// ```rust
+/// use sgm41511::sim::{Adapter, Battery, Simulation};
+/// use sgm41511::types::*;
+/// use sgm41511::SGM41511;
+/// let mut sim = Simulation::new(Battery::new(1000, 0.9));
+/// sim.plug(Adapter::dcp(2000));
+/// SGM41511::new(&mut sim)
+///     .modify_reg05(|reg05| reg05.watchdog_timer_setting = WatchDogTimerSetting::Disabled)
+///     .unwrap();
+/// assert!(sim.advance_until(3 * 3600 * 1000, |sim| {
+///     sim.chip().registers().reg08().unwrap().charge_status == ChargeStatus::Terminated
+/// }));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Simulation {
+    chip: Sgm41511Sim,
+    battery: Battery,
+    adapter: Option<Adapter>,
+    unplugged: Option<Adapter>,
+    temperature_c: f32,
+    system_load_ma: u16,
+    now_ms: u64,
+    step_ms: u32,
+    cycle: Cycle,
+    safety_timer_ms: u64,
+    watchdog_ms: u64,
+    seen_kicks: u32,
+    battery_current_ma: f32,
+    input_current_ma: f32,
+    dpm: Dpm,
+}
+
+impl Simulation {
+    /// A chip out of power-on reset with `battery` attached, no input, at
+    /// 25 °C and t = 0.
+    pub fn new(battery: Battery) -> Self {
+        Simulation {
+            chip: Sgm41511Sim::new(),
+            battery,
+            adapter: None,
+            unplugged: None,
+            temperature_c: 25.0,
+            system_load_ma: 0,
+            now_ms: 0,
+            step_ms: DEFAULT_STEP_MS,
+            cycle: Cycle::Idle,
+            safety_timer_ms: 0,
+            watchdog_ms: 0,
+            seen_kicks: 0,
+            battery_current_ma: 0.0,
+            input_current_ma: 0.0,
+            dpm: Dpm::None,
+        }
+    }
+
+    pub fn chip(&self) -> &Sgm41511Sim {
+        &self.chip
+    }
+
+    pub fn chip_mut(&mut self) -> &mut Sgm41511Sim {
+        &mut self.chip
+    }
+
+    pub fn battery(&self) -> &Battery {
+        &self.battery
+    }
+
+    pub fn battery_mut(&mut self) -> &mut Battery {
+        &mut self.battery
+    }
+
+    pub fn adapter(&self) -> Option<&Adapter> {
+        self.adapter.as_ref()
+    }
+
+    /// Plugs `adapter` into VBUS. Input source detection runs right away and
+    /// sets IINLIM in REG00 for the detected source type.
+    pub fn plug(&mut self, adapter: Adapter) {
+        self.adapter = Some(adapter);
+        self.cycle = Cycle::Idle;
+        let limit = adapter.detected_limit();
+        let reg00 = self.chip.registers().raw(Register::Reg00);
+        self.chip
+            .poke(Register::Reg00, (reg00 & !0x1f) | limit as u8);
+        self.update_registers();
+    }
+
+    pub fn unplug(&mut self) {
+        if let Some(adapter) = self.adapter.take() {
+            self.unplugged = Some(adapter);
+        }
+        self.cycle = Cycle::Idle;
+        self.update_registers();
+    }
+
+    /// Unplugs the adapter, or plugs the last unplugged one back in.
+    pub fn toggle_vbus(&mut self) {
+        match (self.adapter, self.unplugged) {
+            (Some(_), _) => self.unplug(),
+            (None, Some(adapter)) => self.plug(adapter),
+            (None, None) => {}
+        }
+    }
+
+    /// Carries out the VBUS toggles injected into the chip.
+    fn apply_injected(&mut self) {
+        for _ in 0..self.chip.take_vbus_toggles() {
+            self.toggle_vbus();
+        }
+    }
+
+    /// Battery temperature seen through the NTC.
+    pub fn temperature_c(&self) -> f32 {
+        self.temperature_c
+    }
+
+    pub fn set_temperature_c(&mut self, celsius: f32) {
+        self.temperature_c = celsius;
+    }
+
+    pub fn system_load_ma(&self) -> u16 {
+        self.system_load_ma
+    }
+
+    /// Current drawn from SYS by the rest of the system.
+    pub fn set_system_load_ma(&mut self, milliamps: u16) {
+        self.system_load_ma = milliamps;
+    }
+
+    /// Virtual time since the start of the simulation.
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    pub fn step_ms(&self) -> u32 {
+        self.step_ms
+    }
+
+    /// Sets the length of a simulation step, at least 1 ms. Shorter steps
+    /// are more accurate around transitions and slower to run.
+    pub fn set_step_ms(&mut self, step_ms: u32) {
+        self.step_ms = step_ms.max(1);
+    }
+
+    /// Current into the battery in the last step, negative when discharging.
+    pub fn battery_current_ma(&self) -> f32 {
+        self.battery_current_ma
+    }
+
+    /// Current drawn from VBUS in the last step.
+    pub fn input_current_ma(&self) -> f32 {
+        self.input_current_ma
+    }
+
+    /// Battery terminal voltage in the last step.
+    pub fn battery_voltage_mv(&self) -> f32 {
+        self.battery.voltage_at(self.battery_current_ma)
+    }
+
+    /// VBUS in the last step, 0 without an adapter.
+    pub fn bus_voltage_mv(&self) -> f32 {
+        self.adapter
+            .map_or(0.0, |adapter| adapter.voltage_at(self.input_current_ma))
+    }
+
+    /// Runs the model for `duration_ms` of virtual time.
+    pub fn advance(&mut self, duration_ms: u64) {
+        let end = self.now_ms + duration_ms;
+        while self.now_ms < end {
+            let dt = (end - self.now_ms).min(self.step_ms as u64) as u32;
+            self.step(dt);
+        }
+    }
+
+    /// Runs the model until `done` returns `true` after a step, for at most
+    /// `max_ms`. Returns whether `done` was reached.
+    pub fn advance_until(&mut self, max_ms: u64, mut done: impl FnMut(&Self) -> bool) -> bool {
+        let end = self.now_ms + max_ms;
+        while self.now_ms < end {
+            let dt = (end - self.now_ms).min(self.step_ms as u64) as u32;
+            self.step(dt);
+            if done(self) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn step(&mut self, dt_ms: u32) {
+        self.now_ms += dt_ms as u64;
+        self.run_watchdog(dt_ms);
+        self.run_charger(dt_ms);
+        self.update_registers();
+    }
+
+    fn run_watchdog(&mut self, dt_ms: u32) {
+        let kicks = self.chip.watchdog_kicks();
+        if kicks != self.seen_kicks {
+            self.seen_kicks = kicks;
+            self.watchdog_ms = 0;
+        }
+        let reg05 = self
+            .chip
+            .registers()
+            .lenient::<Reg05Values>(Register::Reg05)
+            .value;
+        match reg05.watchdog_timer_setting.as_seconds() {
+            Some(seconds) => {
+                self.watchdog_ms += dt_ms as u64;
+                if self.watchdog_ms >= seconds as u64 * 1000 {
+                    self.chip.expire_watchdog();
+                    self.watchdog_ms = 0;
+                }
+            }
+            None => self.watchdog_ms = 0,
+        }
+    }
+
+    fn run_charger(&mut self, dt_ms: u32) {
+        let map = self.chip.registers();
+        let inputs = Inputs::new(&map, self.adapter, self.temperature_c);
+
+        if !inputs.power_good || !inputs.charge_enabled {
+            self.cycle = Cycle::Idle;
+            self.safety_timer_ms = 0;
+        } else if self.cycle == Cycle::Idle {
+            self.cycle = Cycle::Charging;
+        }
+        if self.cycle == Cycle::Done
+            && self.battery.ocv_mv() < inputs.vreg_mv - inputs.recharge_offset_mv
+        {
+            self.cycle = Cycle::Charging;
+            self.safety_timer_ms = 0;
+        }
+
+        let load = self.system_load_ma as f32;
+        let charging = matches!(self.cycle, Cycle::Charging | Cycle::TopOff { .. })
+            && inputs.input_on
+            && inputs.temperature_ok();
+        let mut battery = if charging {
+            inputs.charge_target_ma(&self.battery)
+        } else {
+            0.0
+        };
+        let constant_voltage = charging && battery < inputs.current_target_ma(&self.battery);
+
+        let (mut input, mut dpm) = (0.0, Dpm::None);
+        if let (Some(adapter), true) = (self.adapter, inputs.input_on) {
+            let vsys = self.battery.voltage_at(battery).max(inputs.sys_min_mv);
+            let needed = vsys * (battery + load) / (adapter.voltage_mv as f32 * EFFICIENCY);
+            let (limit, limited_by) = inputs.input_limit_ma(&adapter, &self.battery);
+            if needed > limit {
+                let available = limit * adapter.voltage_at(limit) * EFFICIENCY / vsys;
+                battery = available - load;
+                input = limit;
+                dpm = limited_by;
+            } else {
+                input = needed;
+            }
+        } else {
+            battery = -load;
+        }
+
+        if self.cycle == Cycle::Charging
+            && constant_voltage
+            && dpm == Dpm::None
+            && inputs.term_enabled
+            && battery < inputs.iterm_ma
+            && !inputs.precharge(&self.battery)
+        {
+            self.cycle = match inputs.top_off_ms {
+                0 => Cycle::Done,
+                remaining_ms => Cycle::TopOff { remaining_ms },
+            };
+        } else if let Cycle::TopOff { remaining_ms } = self.cycle {
+            self.cycle = match remaining_ms.saturating_sub(dt_ms) {
+                0 => Cycle::Done,
+                remaining_ms => Cycle::TopOff { remaining_ms },
+            };
+        }
+
+        if charging && inputs.timer_enabled {
+            let slowed =
+                inputs.timer_2x && (dpm != Dpm::None || inputs.zone == TemperatureZone::Cool);
+            self.safety_timer_ms += if slowed { dt_ms / 2 } else { dt_ms } as u64;
+            if self.safety_timer_ms >= inputs.safety_timer_ms {
+                self.cycle = Cycle::TimerExpired;
+            }
+        }
+
+        self.battery.transfer(battery, dt_ms);
+        self.battery_current_ma = battery;
+        self.input_current_ma = input;
+        self.dpm = dpm;
+    }
+
+    fn update_registers(&mut self) {
+        let map = self.chip.registers();
+        let inputs = Inputs::new(&map, self.adapter, self.temperature_c);
+
+        let precharge = inputs.precharge(&self.battery);
+        let charging = inputs.input_on && inputs.temperature_ok();
+        let charge_status = match self.cycle {
+            Cycle::Charging if charging && precharge => ChargeStatus::Pre,
+            Cycle::Charging if charging => ChargeStatus::Fast,
+            Cycle::TopOff { .. } if charging => ChargeStatus::Terminated,
+            Cycle::Done => ChargeStatus::Terminated,
+            _ => ChargeStatus::Disabled,
+        };
+        let vbus_status = match self.adapter {
+            Some(adapter) if inputs.power_good => adapter.kind,
+            _ => VBUSStatus::NoInput,
+        };
+        self.chip.set_status(u8::from(Reg08Values {
+            vbus_status,
+            charge_status,
+            pg_status: inputs.power_good,
+            therm_status: false,
+            vsys_status: self.battery.ocv_mv() < inputs.sys_min_mv,
+        }));
+
+        let input_dpm = inputs.input_on && self.cycle != Cycle::Idle;
+        self.chip.set_conditions(u8::from(Reg0aValues {
+            vbus_gd: self.adapter.is_some() && !inputs.over_voltage,
+            vindpm_status: input_dpm && self.dpm == Dpm::Voltage,
+            iindpm_status: input_dpm && self.dpm == Dpm::Current,
+            iindet_enabled: false,
+            topoff_active: matches!(self.cycle, Cycle::TopOff { .. }),
+            acov_status: inputs.over_voltage,
+            vindpm_int_mask: false,
+            iindpm_int_mask: false,
+        }));
+
+        let charge_fault = if inputs.over_voltage {
+            ChargeFault::InputFault
+        } else if self.cycle == Cycle::TimerExpired {
+            ChargeFault::ChhargeSafetyTimerExpired
+        } else {
+            ChargeFault::Normal
+        };
+        let ntc_fault = if inputs.power_good {
+            inputs.zone.ntc_fault()
+        } else {
+            0
+        };
+        let faults = u8::from(Reg09Values {
+            watchdog_fault: false,
+            boost_fault: false,
+            charge_fault,
+            bat_fault: self.battery.ocv_mv() > inputs.vreg_mv * 1.04,
+            ntc_fault: NtcFault::Normal,
+        }) | ntc_fault;
+        self.chip.set_present_faults(MODEL_FAULTS, faults);
+    }
+}
+
+/// Which input loop limits the charger.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Dpm {
+    None,
+    Voltage,
+    Current,
+}
+
+/// The settings and conditions of one step, decoded from the registers.
+struct Inputs {
+    zone: TemperatureZone,
+    power_good: bool,
+    over_voltage: bool,
+    input_on: bool,
+    charge_enabled: bool,
+    term_enabled: bool,
+    timer_enabled: bool,
+    timer_2x: bool,
+    safety_timer_ms: u64,
+    top_off_ms: u32,
+    vreg_mv: f32,
+    recharge_offset_mv: f32,
+    sys_min_mv: f32,
+    ichg_ma: f32,
+    iprechg_ma: f32,
+    iterm_ma: f32,
+    iindpm_ma: f32,
+    vindpm_mv: f32,
+    vindpm_tracking_mv: Option<f32>,
+    jeita_current: f32,
+}
+
+impl Inputs {
+    fn new(map: &RegisterMap, adapter: Option<Adapter>, temperature_c: f32) -> Self {
+        let reg00 = map.lenient::<Reg00Values>(Register::Reg00).value;
+        let reg01 = map.lenient::<Reg01Values>(Register::Reg01).value;
+        let reg02 = map.lenient::<Reg02Values>(Register::Reg02).value;
+        let reg03 = map.lenient::<Reg03Values>(Register::Reg03).value;
+        let reg04 = map.lenient::<Reg04Values>(Register::Reg04).value;
+        let reg05 = map.lenient::<Reg05Values>(Register::Reg05).value;
+        let reg06 = map.lenient::<Reg06Values>(Register::Reg06).value;
+        let reg07 = map.lenient::<Reg07Values>(Register::Reg07).value;
+
+        let zone = TemperatureZone::from_celsius(temperature_c);
+        let over_voltage = adapter
+            .is_some_and(|adapter| adapter.voltage_mv >= reg06.ovp_threshold.as_millivolts());
+        let power_good =
+            !over_voltage && adapter.is_some_and(|adapter| adapter.voltage_mv >= VBUS_UVLO_MV);
+        let mut vreg_mv = reg04.charge_voltage_limit.as_millivolts() as f32;
+        if zone == TemperatureZone::Warm
+            && reg07.jeita_voltage_setting == JEITAVoltageSetting::LowerOf4_1V
+        {
+            vreg_mv = vreg_mv.min(JEITA_WARM_VREG_MV);
+        }
+
+        Inputs {
+            zone,
+            power_good,
+            over_voltage,
+            input_on: power_good && !reg00.en_hiz,
+            charge_enabled: reg01.charge_enabled && !reg01.otg_enabled && !reg07.batfet_disabled,
+            term_enabled: reg05.term_enabled,
+            timer_enabled: reg05.timer_enabled,
+            timer_2x: reg07.tmr2x_enabled,
+            safety_timer_ms: match reg05.charge_timer_setting {
+                ChargeTimerSetting::_4Hours => 4 * 3600 * 1000,
+                ChargeTimerSetting::_6Hours => 6 * 3600 * 1000,
+            },
+            top_off_ms: match reg04.top_off_timer {
+                TopOffTimer::Disabled => 0,
+                TopOffTimer::_15Minutes => 15 * 60 * 1000,
+                TopOffTimer::_30Minutes => 30 * 60 * 1000,
+                TopOffTimer::_45Minutes => 45 * 60 * 1000,
+            },
+            vreg_mv,
+            recharge_offset_mv: reg04.battery_recharge_threshold.as_millivolts() as f32,
+            sys_min_mv: reg01.sys_min_voltage.as_millivolts() as f32,
+            ichg_ma: reg02.charge_current.as_milliamps() as f32,
+            iprechg_ma: reg03.pre_charge_current.as_milliamps() as f32,
+            iterm_ma: reg03.term_charge_current.as_milliamps() as f32,
+            iindpm_ma: reg00.input_milliamps_limit.as_milliamps() as f32,
+            vindpm_mv: reg06.vindpm_threshold.as_millivolts() as f32,
+            vindpm_tracking_mv: match reg07.vdpm_battery_tracking {
+                VDPMBatteryVoltageTracking::Disabled => None,
+                VDPMBatteryVoltageTracking::_200mV => Some(200.0),
+                VDPMBatteryVoltageTracking::_250mV => Some(250.0),
+                VDPMBatteryVoltageTracking::_300mV => Some(300.0),
+            },
+            jeita_current: match reg05.jeita_charging_current {
+                JEITAChargingCurrent::_50Percent => 0.5,
+                JEITAChargingCurrent::_20Percent => 0.2,
+            },
+        }
+    }
+
+    fn temperature_ok(&self) -> bool {
+        !matches!(self.zone, TemperatureZone::Cold | TemperatureZone::Hot)
+    }
+
+    fn precharge(&self, battery: &Battery) -> bool {
+        battery.ocv_mv() < BATLOWV_MV
+    }
+
+    /// Constant current setting: IPRECHG or ICHG, reduced in the cool zone.
+    fn current_target_ma(&self, battery: &Battery) -> f32 {
+        if self.precharge(battery) {
+            self.iprechg_ma
+        } else if self.zone == TemperatureZone::Cool {
+            self.ichg_ma * self.jeita_current
+        } else {
+            self.ichg_ma
+        }
+    }
+
+    /// The constant current setting, or less once the cell reaches VREG.
+    fn charge_target_ma(&self, battery: &Battery) -> f32 {
+        let headroom = (self.vreg_mv - battery.ocv_mv()) * 1000.0 / battery.resistance_mohm as f32;
+        self.current_target_ma(battery).min(headroom.max(0.0))
+    }
+
+    /// Most current the input can deliver, and the loop that limits it.
+    fn input_limit_ma(&self, adapter: &Adapter, battery: &Battery) -> (f32, Dpm) {
+        let vindpm = match self.vindpm_tracking_mv {
+            Some(offset) => self.vindpm_mv.max(battery.ocv_mv() + offset),
+            None => self.vindpm_mv,
+        };
+        let sag = if adapter.resistance_mohm == 0 {
+            f32::INFINITY
+        } else {
+            (adapter.voltage_mv as f32 - vindpm).max(0.0) * 1000.0 / adapter.resistance_mohm as f32
+        };
+        let voltage_limit = sag.min(adapter.current_limit_ma as f32);
+        if self.iindpm_ma <= voltage_limit {
+            (self.iindpm_ma, Dpm::Current)
+        } else {
+            (voltage_limit, Dpm::Voltage)
+        }
+    }
+}
+
+impl ErrorType for Simulation {
+    type Error = ErrorKind;
+}
+
+impl embedded_hal::i2c::I2c for Simulation {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let result = embedded_hal::i2c::I2c::transaction(&mut self.chip, address, operations);
+        self.apply_injected();
+        result
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for Simulation {
+    async fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let result = embedded_hal::i2c::I2c::transaction(&mut self.chip, address, operations);
+        self.apply_injected();
+        result
+    }
+}
@@ -0,0 +1,193 @@
+//! Parser for the subset of TOML used by scenario files: top-level
+//! `key = value` pairs, `[[array]]` tables, strings, integers, floats,
+//! booleans and `#` comments.
+
+use std::fmt;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl Value {
+    pub(crate) const fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Boolean(_) => "boolean",
+        }
+    }
+}
+
+/// A `key = value` line.
+#[derive(PartialEq, Clone, Debug)]
+pub(crate) struct Entry {
+    pub key: String,
+    pub value: Value,
+    pub line: usize,
+}
+
+/// The top-level table or one `[[name]]` table.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub(crate) struct Table {
+    pub name: String,
+    /// Line of the `[[name]]` header, 0 for the top-level table.
+    pub line: usize,
+    pub entries: Vec<Entry>,
+}
+
+/// A scenario file that could not be read.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseError {
+    /// 1-based line of the problem.
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits `text` into the top-level table followed by the `[[array]]`
+/// tables in file order.
+pub(crate) fn parse(text: &str) -> Result<Vec<Table>, ParseError> {
+    let mut tables = Vec::from([Table::default()]);
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let content = strip_comment(raw).trim();
+        if content.is_empty() {
+            continue;
+        }
+        if let Some(header) = content.strip_prefix("[[") {
+            let name = header
+                .strip_suffix("]]")
+                .map(str::trim)
+                .filter(|name| is_bare_key(name))
+                .ok_or_else(|| ParseError::new(line, "malformed table header"))?;
+            tables.push(Table {
+                name: name.to_string(),
+                line,
+                entries: Vec::new(),
+            });
+            continue;
+        }
+        if content.starts_with('[') {
+            return Err(ParseError::new(line, "only [[array]] tables are supported"));
+        }
+        let (key, value) = content
+            .split_once('=')
+            .ok_or_else(|| ParseError::new(line, "expected `key = value`"))?;
+        let key = key.trim();
+        if !is_bare_key(key) {
+            return Err(ParseError::new(line, "invalid key"));
+        }
+        let value =
+            parse_value(value.trim()).ok_or_else(|| ParseError::new(line, "invalid value"))?;
+        let table = tables
+            .last_mut()
+            .expect("the top-level table is always there");
+        if table.entries.iter().any(|entry| entry.key == key) {
+            return Err(ParseError::new(
+                line,
+                std::format!("duplicate key `{}`", key),
+            ));
+        }
+        table.entries.push(Entry {
+            key: key.to_string(),
+            value,
+            line,
+        });
+    }
+    Ok(tables)
+}
+
+/// Removes a trailing `# comment`, leaving `#` inside strings alone.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn parse_value(text: &str) -> Option<Value> {
+    if let Some(body) = text.strip_prefix('"') {
+        return parse_string(body).map(Value::String);
+    }
+    match text {
+        "true" => return Some(Value::Boolean(true)),
+        "false" => return Some(Value::Boolean(false)),
+        _ => {}
+    }
+    let digits: String = text.chars().filter(|c| *c != '_').collect();
+    let (negative, unsigned) = match digits.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, digits.strip_prefix('+').unwrap_or(&digits)),
+    };
+    let integer = if let Some(hex) = unsigned.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = unsigned.strip_prefix("0b") {
+        i64::from_str_radix(binary, 2).ok()
+    } else if unsigned.chars().all(|c| c.is_ascii_digit()) {
+        unsigned.parse().ok()
+    } else {
+        None
+    };
+    match integer {
+        Some(value) => Some(Value::Integer(if negative { -value } else { value })),
+        None => digits.parse().ok().map(Value::Float),
+    }
+}
+
+/// Parses the rest of a basic string after its opening quote.
+fn parse_string(body: &str) -> Option<String> {
+    let mut value = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return chars.as_str().trim().is_empty().then_some(value),
+            '\\' => value.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                '"' => '"',
+                '\\' => '\\',
+                _ => return None,
+            }),
+            c => value.push(c),
+        }
+    }
+    None
+}
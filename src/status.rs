@@ -0,0 +1,147 @@
+//! High-level charger state.
+//!
+//! [`ChargerStatus`] combines REG08 - REG0A into a single [`ChargeState`]
+//! answering whether the battery is charging, full or not charging, and
+//! why.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sgm41511::register_map::StatusRegisters;
+//! use sgm41511::status::*;
+//! // DCP adapter, fast charging.
+//! let status = ChargerStatus::from(StatusRegisters::from_raw([0x74, 0x00, 0x80]));
+//! assert_eq!(status.state, ChargeState::Charging(ChargePhase::Fast));
+//! assert!(status.state.is_charging());
+//!
+//! // Input present but NTC hot.
+//! let status = ChargerStatus::from(StatusRegisters::from_raw([0x64, 0x06, 0x80]));
+//! assert_eq!(status.state, ChargeState::Faulted(FaultReason::NtcHot));
+//! ```
+
+use crate::register_map::StatusRegisters;
+use crate::types::*;
+
+/// Phase of an ongoing charge.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChargePhase {
+    /// Pre-charge (or trickle charge) of a deeply discharged battery.
+    Pre,
+    /// Constant current or constant voltage fast charge.
+    Fast,
+}
+
+/// Why the charger is not charging although nothing is faulted.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SuspendReason {
+    /// An input is connected but not good enough to charge from.
+    PowerNotGood,
+    /// Charging is disabled, by CHG_CONFIG, HIZ mode or the CE pin.
+    ChargeDisabled,
+    /// The charger runs in OTG (boost) mode.
+    Otg,
+}
+
+/// Why the charger stopped.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FaultReason {
+    /// Input over-voltage or poor source.
+    Input,
+    ThermalShutdown,
+    /// The fast charge safety timer expired.
+    SafetyTimer,
+    BatteryOverVoltage,
+    NtcCold,
+    NtcHot,
+    /// OTG mode overloaded or over-voltage.
+    Boost,
+}
+
+/// Derived state of the charger.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ChargeState {
+    /// No input, the system runs from the battery.
+    NotConnected,
+    Charging(ChargePhase),
+    /// Charge terminated.
+    Full,
+    /// Charge terminated, charging goes on at ITERM until the top-off timer
+    /// expires.
+    TopOff,
+    Suspended(SuspendReason),
+    Faulted(FaultReason),
+}
+
+impl ChargeState {
+    pub const fn is_charging(&self) -> bool {
+        matches!(self, ChargeState::Charging(_) | ChargeState::TopOff)
+    }
+
+    /// Derives the state from the decoded status registers.
+    pub fn from_registers(reg08: &Reg08Values, reg09: &Reg09Values, reg0a: &Reg0aValues) -> Self {
+        if reg08.vbus_status == VBUSStatus::OTG {
+            return if reg09.boost_fault {
+                ChargeState::Faulted(FaultReason::Boost)
+            } else {
+                ChargeState::Suspended(SuspendReason::Otg)
+            };
+        }
+        if reg08.vbus_status == VBUSStatus::NoInput && !reg0a.vbus_gd {
+            return ChargeState::NotConnected;
+        }
+        let fault = match reg09.charge_fault {
+            ChargeFault::InputFault => Some(FaultReason::Input),
+            ChargeFault::ThermalShutdown => Some(FaultReason::ThermalShutdown),
+            ChargeFault::ChhargeSafetyTimerExpired => Some(FaultReason::SafetyTimer),
+            ChargeFault::Normal if reg09.bat_fault => Some(FaultReason::BatteryOverVoltage),
+            ChargeFault::Normal => match reg09.ntc_fault {
+                NtcFault::Cold => Some(FaultReason::NtcCold),
+                NtcFault::Hot => Some(FaultReason::NtcHot),
+                _ => None,
+            },
+        };
+        if let Some(reason) = fault {
+            return ChargeState::Faulted(reason);
+        }
+        match reg08.charge_status {
+            ChargeStatus::Pre => ChargeState::Charging(ChargePhase::Pre),
+            ChargeStatus::Fast => ChargeState::Charging(ChargePhase::Fast),
+            ChargeStatus::Terminated if reg0a.topoff_active => ChargeState::TopOff,
+            ChargeStatus::Terminated => ChargeState::Full,
+            ChargeStatus::Disabled if !reg08.pg_status => {
+                ChargeState::Suspended(SuspendReason::PowerNotGood)
+            }
+            ChargeStatus::Disabled => ChargeState::Suspended(SuspendReason::ChargeDisabled),
+        }
+    }
+}
+
+/// The status registers REG08 - REG0A with the state derived from them.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChargerStatus {
+    pub state: ChargeState,
+    pub reg08: Reg08Values,
+    pub reg09: Reg09Values,
+    pub reg0a: Reg0aValues,
+}
+
+/// Decodes leniently, reserved codes never fail a status read.
+impl From<StatusRegisters> for ChargerStatus {
+    fn from(registers: StatusRegisters) -> Self {
+        let [reg08, reg09, reg0a] = *registers.as_raw();
+        let reg08 = Reg08Values::decode_lenient(reg08);
+        let reg09 = Reg09Values::decode_lenient(reg09);
+        let reg0a = Reg0aValues::decode_lenient(reg0a);
+        ChargerStatus {
+            state: ChargeState::from_registers(&reg08, &reg09, &reg0a),
+            reg08,
+            reg09,
+            reg0a,
+        }
+    }
+}
@@ -1,3 +1,69 @@
+/// A raw value that does not map to any setting defined in the datasheet.
+///
+/// Returned by the `TryFrom<u8>` impls of the field enums and the
+/// `RegXXValues` structs. For a whole register it holds the register byte.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidValue(pub u8);
+
+/// Decoding of a register byte that never fails.
+///
+/// Field values that are not defined in the datasheet are clamped to the
+/// nearest valid setting (or to the normal state for status codes).
+pub trait DecodeLenient: Sized {
+    fn decode_lenient(value: u8) -> Self;
+}
+
+/// A leniently decoded register together with the raw byte it came from, so
+/// that reserved or unknown bits are not lost.
+///
+/// # Examples
+///
+/// ```rust
+/// use sgm41511::types::*;
+/// let reg = Lenient::<Reg02Values>::from(0b00111111);
+/// assert_eq!(reg.value.charge_current, ChargeCurrent::_3000mA);
+/// assert_eq!(reg.raw, 0b00111111);
+/// assert!(!reg.is_exact());
+/// ```
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Lenient<T> {
+    pub value: T,
+    pub raw: u8,
+}
+
+impl<T: DecodeLenient> From<u8> for Lenient<T> {
+    fn from(raw: u8) -> Self {
+        Lenient {
+            value: T::decode_lenient(raw),
+            raw,
+        }
+    }
+}
+
+impl<T: Copy + Into<u8>> Lenient<T> {
+    /// Returns `true` if encoding `value` gives back exactly `raw`.
+    pub fn is_exact(&self) -> bool {
+        self.value.into() == self.raw
+    }
+}
+
+/// The value of a register before and after a read-modify-write.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Modified<T> {
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: PartialEq> Modified<T> {
+    /// Returns `true` if the register was written.
+    pub fn changed(&self) -> bool {
+        self.old != self.new
+    }
+}
+
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -71,42 +137,44 @@ pub enum InputCurrentLimit {
 /// Convert from u8 to InputCurrentLimit
 ///
 /// Range: 0x00 - 0x1f
-impl From<u8> for InputCurrentLimit {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for InputCurrentLimit {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => InputCurrentLimit::_100mA,
-            0x01 => InputCurrentLimit::_200mA,
-            0x02 => InputCurrentLimit::_300mA,
-            0x03 => InputCurrentLimit::_400mA,
-            0x04 => InputCurrentLimit::_500mA,
-            0x05 => InputCurrentLimit::_600mA,
-            0x06 => InputCurrentLimit::_700mA,
-            0x07 => InputCurrentLimit::_800mA,
-            0x08 => InputCurrentLimit::_900mA,
-            0x09 => InputCurrentLimit::_1000mA,
-            0x0a => InputCurrentLimit::_1100mA,
-            0x0b => InputCurrentLimit::_1200mA,
-            0x0c => InputCurrentLimit::_1300mA,
-            0x0d => InputCurrentLimit::_1400mA,
-            0x0e => InputCurrentLimit::_1500mA,
-            0x0f => InputCurrentLimit::_1600mA,
-            0x10 => InputCurrentLimit::_1700mA,
-            0x11 => InputCurrentLimit::_1800mA,
-            0x12 => InputCurrentLimit::_1900mA,
-            0x13 => InputCurrentLimit::_2000mA,
-            0x14 => InputCurrentLimit::_2100mA,
-            0x15 => InputCurrentLimit::_2200mA,
-            0x16 => InputCurrentLimit::_2300mA,
-            0x17 => InputCurrentLimit::_2400mA,
-            0x18 => InputCurrentLimit::_2500mA,
-            0x19 => InputCurrentLimit::_2600mA,
-            0x1a => InputCurrentLimit::_2700mA,
-            0x1b => InputCurrentLimit::_2800mA,
-            0x1c => InputCurrentLimit::_2900mA,
-            0x1d => InputCurrentLimit::_3000mA,
-            0x1e => InputCurrentLimit::_3100mA,
-            0x1f => InputCurrentLimit::_3200mA,
-            _ => InputCurrentLimit::_3200mA,
+            0x00 => Ok(InputCurrentLimit::_100mA),
+            0x01 => Ok(InputCurrentLimit::_200mA),
+            0x02 => Ok(InputCurrentLimit::_300mA),
+            0x03 => Ok(InputCurrentLimit::_400mA),
+            0x04 => Ok(InputCurrentLimit::_500mA),
+            0x05 => Ok(InputCurrentLimit::_600mA),
+            0x06 => Ok(InputCurrentLimit::_700mA),
+            0x07 => Ok(InputCurrentLimit::_800mA),
+            0x08 => Ok(InputCurrentLimit::_900mA),
+            0x09 => Ok(InputCurrentLimit::_1000mA),
+            0x0a => Ok(InputCurrentLimit::_1100mA),
+            0x0b => Ok(InputCurrentLimit::_1200mA),
+            0x0c => Ok(InputCurrentLimit::_1300mA),
+            0x0d => Ok(InputCurrentLimit::_1400mA),
+            0x0e => Ok(InputCurrentLimit::_1500mA),
+            0x0f => Ok(InputCurrentLimit::_1600mA),
+            0x10 => Ok(InputCurrentLimit::_1700mA),
+            0x11 => Ok(InputCurrentLimit::_1800mA),
+            0x12 => Ok(InputCurrentLimit::_1900mA),
+            0x13 => Ok(InputCurrentLimit::_2000mA),
+            0x14 => Ok(InputCurrentLimit::_2100mA),
+            0x15 => Ok(InputCurrentLimit::_2200mA),
+            0x16 => Ok(InputCurrentLimit::_2300mA),
+            0x17 => Ok(InputCurrentLimit::_2400mA),
+            0x18 => Ok(InputCurrentLimit::_2500mA),
+            0x19 => Ok(InputCurrentLimit::_2600mA),
+            0x1a => Ok(InputCurrentLimit::_2700mA),
+            0x1b => Ok(InputCurrentLimit::_2800mA),
+            0x1c => Ok(InputCurrentLimit::_2900mA),
+            0x1d => Ok(InputCurrentLimit::_3000mA),
+            0x1e => Ok(InputCurrentLimit::_3100mA),
+            0x1f => Ok(InputCurrentLimit::_3200mA),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -126,27 +194,45 @@ pub struct Reg00Values {
     pub input_milliamps_limit: InputCurrentLimit,
 }
 
-/// Converts an `u8` to `Reg00Values`
+impl Reg00Values {
+    /// Power-on reset value (0x17).
+    pub const RESET: Self = Reg00Values {
+        en_hiz: false,
+        en_ichg_mon: true,
+        input_milliamps_limit: InputCurrentLimit::_2400mA,
+    };
+}
+
+/// Converts `u8` to `Reg00Values`
 ///
 /// # Examples
 ///
 /// ```rust
 /// use sgm41511::types::*;
-/// let values = Reg00Values::from(0b00010111);
+/// let values = Reg00Values::try_from(0b00010111).unwrap();
 /// assert_eq!(values, Reg00Values { en_hiz: false, en_ichg_mon: true, input_milliamps_limit: InputCurrentLimit::_2400mA });
 ///
-/// let values = Reg00Values::from(0b11111111);
+/// let values = Reg00Values::try_from(0b11111111).unwrap();
 /// assert_eq!(values, Reg00Values { en_hiz: true, en_ichg_mon: false, input_milliamps_limit: InputCurrentLimit::_3200mA });
 ///
-/// let values = Reg00Values::from(0b00000000);
+/// let values = Reg00Values::try_from(0b00000000).unwrap();
 /// assert_eq!(values, Reg00Values { en_hiz: false, en_ichg_mon: true, input_milliamps_limit: InputCurrentLimit::_100mA });
 /// ```
-impl From<u8> for Reg00Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg00Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg00Values::decode_lenient(value))
+    }
+}
+
+impl DecodeLenient for Reg00Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg00Values {
             en_hiz: value & 0x80 != 0,
             en_ichg_mon: value & 0x60 == 0,
-            input_milliamps_limit: InputCurrentLimit::from(value & 0x1f),
+            input_milliamps_limit: InputCurrentLimit::try_from(value & 0x1f)
+                .unwrap_or(InputCurrentLimit::_3200mA),
         }
     }
 }
@@ -166,16 +252,16 @@ impl From<u8> for Reg00Values {
 /// let values: u8 = Reg00Values { en_hiz: false, en_ichg_mon: true, input_milliamps_limit: InputCurrentLimit::_100mA }.into();
 /// assert_eq!(values, 0b00000000);
 /// ```
-impl<'a> Into<u8> for Reg00Values {
-    fn into(self) -> u8 {
+impl From<Reg00Values> for u8 {
+    fn from(values: Reg00Values) -> Self {
         let mut value = 0u8;
-        if self.en_hiz {
+        if values.en_hiz {
             value |= 0x80;
         }
-        if !self.en_ichg_mon {
+        if !values.en_ichg_mon {
             value |= 0x60;
         }
-        value |= self.input_milliamps_limit as u8;
+        value |= values.input_milliamps_limit as u8;
         value
     }
 }
@@ -202,6 +288,27 @@ pub enum MinSystemVoltage {
     _3_7V = 0b111,
 }
 
+/// Converts `u8` to `MinSystemVoltage`
+///
+/// Range: 0x00 - 0x07
+impl TryFrom<u8> for MinSystemVoltage {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0b000 => Ok(MinSystemVoltage::_2_6V),
+            0b001 => Ok(MinSystemVoltage::_2_8V),
+            0b010 => Ok(MinSystemVoltage::_3_0V),
+            0b011 => Ok(MinSystemVoltage::_3_2V),
+            0b100 => Ok(MinSystemVoltage::_3_4V),
+            0b101 => Ok(MinSystemVoltage::_3_5V),
+            0b110 => Ok(MinSystemVoltage::_3_6V),
+            0b111 => Ok(MinSystemVoltage::_3_7V),
+            _ => Err(InvalidValue(value)),
+        }
+    }
+}
+
 #[repr(u8)]
 #[derive(PartialEq, Clone, Copy, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -212,6 +319,21 @@ pub enum MinBatteryVoltageForOtG {
     _2_6V = 1,
 }
 
+/// Converts `u8` to `MinBatteryVoltageForOtG`
+///
+/// Range: 0x00 - 0x01
+impl TryFrom<u8> for MinBatteryVoltageForOtG {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(MinBatteryVoltageForOtG::_2_95V),
+            0x01 => Ok(MinBatteryVoltageForOtG::_2_6V),
+            _ => Err(InvalidValue(value)),
+        }
+    }
+}
+
 #[derive(PartialEq, Clone, Copy, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Reg01Values {
@@ -226,13 +348,25 @@ pub struct Reg01Values {
     pub min_bat_sel: MinBatteryVoltageForOtG,
 }
 
+impl Reg01Values {
+    /// Power-on reset value (0x1a).
+    pub const RESET: Self = Reg01Values {
+        pfm_disabled: false,
+        watchdog_reset: false,
+        otg_enabled: false,
+        charge_enabled: true,
+        sys_min_voltage: MinSystemVoltage::_3_5V,
+        min_bat_sel: MinBatteryVoltageForOtG::_2_95V,
+    };
+}
+
 /// Converts `u8` to `Reg01Values`
 ///
 /// # Examples
 ///
 /// ```rust
 /// use sgm41511::types::*;
-/// let values = Reg01Values::from(0b00011010);
+/// let values = Reg01Values::try_from(0b00011010).unwrap();
 /// assert_eq!(values, Reg01Values {
 ///     pfm_disabled: false,
 ///     watchdog_reset: false,
@@ -242,7 +376,7 @@ pub struct Reg01Values {
 ///     min_bat_sel: MinBatteryVoltageForOtG::_2_95V,
 /// });
 ///
-/// let values = Reg01Values::from(0b11111111);
+/// let values = Reg01Values::try_from(0b11111111).unwrap();
 /// assert_eq!(values, Reg01Values {
 ///     pfm_disabled: true,
 ///     watchdog_reset: true,
@@ -252,7 +386,7 @@ pub struct Reg01Values {
 ///     min_bat_sel: MinBatteryVoltageForOtG::_2_6V,
 /// });
 ///
-/// let values = Reg01Values::from(0b00000000);
+/// let values = Reg01Values::try_from(0b00000000).unwrap();
 /// assert_eq!(values, Reg01Values {
 ///     pfm_disabled: false,
 ///     watchdog_reset: false,
@@ -262,27 +396,25 @@ pub struct Reg01Values {
 ///     min_bat_sel: MinBatteryVoltageForOtG::_2_95V,
 /// });
 /// ```
-impl From<u8> for Reg01Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg01Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg01Values::decode_lenient(value))
+    }
+}
+
+impl DecodeLenient for Reg01Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg01Values {
             pfm_disabled: value & 0x80 != 0,
             watchdog_reset: value & 0x40 != 0,
             otg_enabled: value & 0x20 != 0,
             charge_enabled: value & 0x10 != 0,
-            sys_min_voltage: match (value & 0x0e) >> 1 {
-                0b000 => MinSystemVoltage::_2_6V,
-                0b001 => MinSystemVoltage::_2_8V,
-                0b010 => MinSystemVoltage::_3_0V,
-                0b011 => MinSystemVoltage::_3_2V,
-                0b100 => MinSystemVoltage::_3_4V,
-                0b101 => MinSystemVoltage::_3_5V,
-                0b110 => MinSystemVoltage::_3_6V,
-                _ => MinSystemVoltage::_3_7V,
-            },
-            min_bat_sel: match value & 0x01 {
-                0 => MinBatteryVoltageForOtG::_2_95V,
-                _ => MinBatteryVoltageForOtG::_2_6V,
-            },
+            sys_min_voltage: MinSystemVoltage::try_from((value & 0x0e) >> 1)
+                .unwrap_or(MinSystemVoltage::_3_7V),
+            min_bat_sel: MinBatteryVoltageForOtG::try_from(value & 0x01)
+                .unwrap_or(MinBatteryVoltageForOtG::_2_6V),
         }
     }
 }
@@ -323,23 +455,23 @@ impl From<u8> for Reg01Values {
 /// }.into();
 /// assert_eq!(values, 0b00000000);
 /// ```
-impl<'a> Into<u8> for Reg01Values {
-    fn into(self) -> u8 {
+impl From<Reg01Values> for u8 {
+    fn from(values: Reg01Values) -> Self {
         let mut value = 0u8;
-        if self.pfm_disabled {
+        if values.pfm_disabled {
             value |= 0x80;
         }
-        if self.watchdog_reset {
+        if values.watchdog_reset {
             value |= 0x40;
         }
-        if self.otg_enabled {
+        if values.otg_enabled {
             value |= 0x20;
         }
-        if self.charge_enabled {
+        if values.charge_enabled {
             value |= 0x10;
         }
-        value |= (self.sys_min_voltage as u8) << 1;
-        value |= self.min_bat_sel as u8;
+        value |= (values.sys_min_voltage as u8) << 1;
+        value |= values.min_bat_sel as u8;
 
         value
     }
@@ -355,6 +487,21 @@ pub enum BoostCurrentLimit {
     _1_25A = 1,
 }
 
+/// Converts `u8` to `BoostCurrentLimit`
+///
+/// Range: 0x00 - 0x01
+impl TryFrom<u8> for BoostCurrentLimit {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(BoostCurrentLimit::_0_5A),
+            0x01 => Ok(BoostCurrentLimit::_1_25A),
+            _ => Err(InvalidValue(value)),
+        }
+    }
+}
+
 #[repr(u8)]
 #[derive(PartialEq, Clone, Copy, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -365,6 +512,21 @@ pub enum Q1FullOnMode {
     Efficiency = 1,
 }
 
+/// Converts `u8` to `Q1FullOnMode`
+///
+/// Range: 0x00 - 0x01
+impl TryFrom<u8> for Q1FullOnMode {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(Q1FullOnMode::Accuracy),
+            0x01 => Ok(Q1FullOnMode::Efficiency),
+            _ => Err(InvalidValue(value)),
+        }
+    }
+}
+
 #[repr(u8)]
 #[derive(PartialEq, Clone, Copy, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -476,62 +638,64 @@ pub enum ChargeCurrent {
 /// Converts an `u8` to `ChargeCurrent`
 ///
 /// Range: 0x00 - 0x32
-/// Values above 0x32 will be mapped to 0x32
-impl From<u8> for ChargeCurrent {
-    fn from(value: u8) -> Self {
-        match value & 0x3f {
-            0x00 => ChargeCurrent::_0mA,
-            0x01 => ChargeCurrent::_60mA,
-            0x02 => ChargeCurrent::_120mA,
-            0x03 => ChargeCurrent::_180mA,
-            0x04 => ChargeCurrent::_240mA,
-            0x05 => ChargeCurrent::_300mA,
-            0x06 => ChargeCurrent::_360mA,
-            0x07 => ChargeCurrent::_420mA,
-            0x08 => ChargeCurrent::_480mA,
-            0x09 => ChargeCurrent::_540mA,
-            0x0a => ChargeCurrent::_600mA,
-            0x0b => ChargeCurrent::_660mA,
-            0x0c => ChargeCurrent::_720mA,
-            0x0d => ChargeCurrent::_780mA,
-            0x0e => ChargeCurrent::_840mA,
-            0x0f => ChargeCurrent::_900mA,
-            0x10 => ChargeCurrent::_960mA,
-            0x11 => ChargeCurrent::_1020mA,
-            0x12 => ChargeCurrent::_1080mA,
-            0x13 => ChargeCurrent::_1140mA,
-            0x14 => ChargeCurrent::_1200mA,
-            0x15 => ChargeCurrent::_1260mA,
-            0x16 => ChargeCurrent::_1320mA,
-            0x17 => ChargeCurrent::_1380mA,
-            0x18 => ChargeCurrent::_1440mA,
-            0x19 => ChargeCurrent::_1500mA,
-            0x1a => ChargeCurrent::_1560mA,
-            0x1b => ChargeCurrent::_1620mA,
-            0x1c => ChargeCurrent::_1680mA,
-            0x1d => ChargeCurrent::_1740mA,
-            0x1e => ChargeCurrent::_1800mA,
-            0x1f => ChargeCurrent::_1860mA,
-            0x20 => ChargeCurrent::_1920mA,
-            0x21 => ChargeCurrent::_1980mA,
-            0x22 => ChargeCurrent::_2040mA,
-            0x23 => ChargeCurrent::_2100mA,
-            0x24 => ChargeCurrent::_2160mA,
-            0x25 => ChargeCurrent::_2220mA,
-            0x26 => ChargeCurrent::_2280mA,
-            0x27 => ChargeCurrent::_2340mA,
-            0x28 => ChargeCurrent::_2400mA,
-            0x29 => ChargeCurrent::_2460mA,
-            0x2a => ChargeCurrent::_2520mA,
-            0x2b => ChargeCurrent::_2580mA,
-            0x2c => ChargeCurrent::_2640mA,
-            0x2d => ChargeCurrent::_2700mA,
-            0x2e => ChargeCurrent::_2760mA,
-            0x2f => ChargeCurrent::_2820mA,
-            0x30 => ChargeCurrent::_2880mA,
-            0x31 => ChargeCurrent::_2940mA,
-            0x32 => ChargeCurrent::_3000mA,
-            _ => ChargeCurrent::_3000mA,
+/// Values above 0x32 are rejected
+impl TryFrom<u8> for ChargeCurrent {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(ChargeCurrent::_0mA),
+            0x01 => Ok(ChargeCurrent::_60mA),
+            0x02 => Ok(ChargeCurrent::_120mA),
+            0x03 => Ok(ChargeCurrent::_180mA),
+            0x04 => Ok(ChargeCurrent::_240mA),
+            0x05 => Ok(ChargeCurrent::_300mA),
+            0x06 => Ok(ChargeCurrent::_360mA),
+            0x07 => Ok(ChargeCurrent::_420mA),
+            0x08 => Ok(ChargeCurrent::_480mA),
+            0x09 => Ok(ChargeCurrent::_540mA),
+            0x0a => Ok(ChargeCurrent::_600mA),
+            0x0b => Ok(ChargeCurrent::_660mA),
+            0x0c => Ok(ChargeCurrent::_720mA),
+            0x0d => Ok(ChargeCurrent::_780mA),
+            0x0e => Ok(ChargeCurrent::_840mA),
+            0x0f => Ok(ChargeCurrent::_900mA),
+            0x10 => Ok(ChargeCurrent::_960mA),
+            0x11 => Ok(ChargeCurrent::_1020mA),
+            0x12 => Ok(ChargeCurrent::_1080mA),
+            0x13 => Ok(ChargeCurrent::_1140mA),
+            0x14 => Ok(ChargeCurrent::_1200mA),
+            0x15 => Ok(ChargeCurrent::_1260mA),
+            0x16 => Ok(ChargeCurrent::_1320mA),
+            0x17 => Ok(ChargeCurrent::_1380mA),
+            0x18 => Ok(ChargeCurrent::_1440mA),
+            0x19 => Ok(ChargeCurrent::_1500mA),
+            0x1a => Ok(ChargeCurrent::_1560mA),
+            0x1b => Ok(ChargeCurrent::_1620mA),
+            0x1c => Ok(ChargeCurrent::_1680mA),
+            0x1d => Ok(ChargeCurrent::_1740mA),
+            0x1e => Ok(ChargeCurrent::_1800mA),
+            0x1f => Ok(ChargeCurrent::_1860mA),
+            0x20 => Ok(ChargeCurrent::_1920mA),
+            0x21 => Ok(ChargeCurrent::_1980mA),
+            0x22 => Ok(ChargeCurrent::_2040mA),
+            0x23 => Ok(ChargeCurrent::_2100mA),
+            0x24 => Ok(ChargeCurrent::_2160mA),
+            0x25 => Ok(ChargeCurrent::_2220mA),
+            0x26 => Ok(ChargeCurrent::_2280mA),
+            0x27 => Ok(ChargeCurrent::_2340mA),
+            0x28 => Ok(ChargeCurrent::_2400mA),
+            0x29 => Ok(ChargeCurrent::_2460mA),
+            0x2a => Ok(ChargeCurrent::_2520mA),
+            0x2b => Ok(ChargeCurrent::_2580mA),
+            0x2c => Ok(ChargeCurrent::_2640mA),
+            0x2d => Ok(ChargeCurrent::_2700mA),
+            0x2e => Ok(ChargeCurrent::_2760mA),
+            0x2f => Ok(ChargeCurrent::_2820mA),
+            0x30 => Ok(ChargeCurrent::_2880mA),
+            0x31 => Ok(ChargeCurrent::_2940mA),
+            0x32 => Ok(ChargeCurrent::_3000mA),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -544,32 +708,56 @@ pub struct Reg02Values {
     pub charge_current: ChargeCurrent,
 }
 
+impl Reg02Values {
+    /// Power-on reset value (0xa2).
+    pub const RESET: Self = Reg02Values {
+        boost_current_limit: BoostCurrentLimit::_1_25A,
+        q1_full_on: Q1FullOnMode::Accuracy,
+        charge_current: ChargeCurrent::_2040mA,
+    };
+}
+
 /// Converts `u8` to `Reg02Values`
 ///
 /// # Examples
 ///
 /// ```rust
 /// use sgm41511::types::*;
-/// let values = Reg02Values::from(0b10100010);
+/// let values = Reg02Values::try_from(0b10100010).unwrap();
 /// assert_eq!(values, Reg02Values { boost_current_limit: BoostCurrentLimit::_1_25A, q1_full_on: Q1FullOnMode::Accuracy, charge_current: ChargeCurrent::_2040mA });
 ///
-/// let values = Reg02Values::from(0b00000000);
+/// let values = Reg02Values::try_from(0b00000000).unwrap();
 /// assert_eq!(values, Reg02Values { boost_current_limit: BoostCurrentLimit::_0_5A, q1_full_on: Q1FullOnMode::Accuracy, charge_current: ChargeCurrent::_0mA });
 ///
-/// let values = Reg02Values::from(0b11110010);
+/// let values = Reg02Values::try_from(0b11110010).unwrap();
 /// assert_eq!(values, Reg02Values { boost_current_limit: BoostCurrentLimit::_1_25A, q1_full_on: Q1FullOnMode::Efficiency, charge_current: ChargeCurrent::_3000mA });
 /// ```
-impl From<u8> for Reg02Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg02Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let charge_current =
+            ChargeCurrent::try_from(value & 0x3f).map_err(|_| InvalidValue(value))?;
+        Ok(Self {
+            charge_current,
+            ..Self::decode_lenient(value)
+        })
+    }
+}
+
+/// ICHG codes above 0x32 decode as 3000 mA.
+impl DecodeLenient for Reg02Values {
+    fn decode_lenient(value: u8) -> Self {
         let boost_current_limit = match (value & 0x80) >> 7 {
             0 => BoostCurrentLimit::_0_5A,
             _ => BoostCurrentLimit::_1_25A,
         };
-        let q1_full_on = match (value & 0x60) >> 6 {
+        let q1_full_on = match (value & 0x40) >> 6 {
             0 => Q1FullOnMode::Accuracy,
             _ => Q1FullOnMode::Efficiency,
         };
-        let charge_current = (value & 0x3f).into();
+        let charge_current =
+            ChargeCurrent::try_from(value & 0x3f).unwrap_or(ChargeCurrent::_3000mA);
         Self {
             boost_current_limit,
             q1_full_on,
@@ -593,17 +781,17 @@ impl From<u8> for Reg02Values {
 /// let values: u8 = Reg02Values { boost_current_limit: BoostCurrentLimit::_1_25A, q1_full_on: Q1FullOnMode::Efficiency, charge_current: ChargeCurrent::_3000mA }.into();
 /// assert_eq!(values, 0b11110010);
 /// ```
-impl Into<u8> for Reg02Values {
-    fn into(self) -> u8 {
+impl From<Reg02Values> for u8 {
+    fn from(values: Reg02Values) -> Self {
         let mut value = 0u8;
-        if self.boost_current_limit == BoostCurrentLimit::_1_25A {
+        if values.boost_current_limit == BoostCurrentLimit::_1_25A {
             value |= 0x80;
         }
-        if self.q1_full_on == Q1FullOnMode::Efficiency {
-            value |= 0x60;
+        if values.q1_full_on == Q1FullOnMode::Efficiency {
+            value |= 0x40;
         }
 
-        value |= self.charge_current as u8;
+        value |= values.charge_current as u8;
         value
     }
 }
@@ -643,24 +831,26 @@ pub enum PreChargeCurrent {
 /// Converts `u8` to `PreChargeCurrent`
 ///
 /// Range: 0x00 - 0x0c (60 - 780 mA)
-/// Values above 0x0c (780mA) are clamped to 0x0c (780mA)
-impl From<u8> for PreChargeCurrent {
-    fn from(value: u8) -> Self {
+/// Values above 0x0c (780mA) are rejected
+impl TryFrom<u8> for PreChargeCurrent {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => PreChargeCurrent::_60mA,
-            0x01 => PreChargeCurrent::_120mA,
-            0x02 => PreChargeCurrent::_180mA,
-            0x03 => PreChargeCurrent::_240mA,
-            0x04 => PreChargeCurrent::_300mA,
-            0x05 => PreChargeCurrent::_360mA,
-            0x06 => PreChargeCurrent::_420mA,
-            0x07 => PreChargeCurrent::_480mA,
-            0x08 => PreChargeCurrent::_540mA,
-            0x09 => PreChargeCurrent::_600mA,
-            0x0a => PreChargeCurrent::_660mA,
-            0x0b => PreChargeCurrent::_720mA,
-            0x0c => PreChargeCurrent::_780mA,
-            _ => PreChargeCurrent::_780mA,
+            0x00 => Ok(PreChargeCurrent::_60mA),
+            0x01 => Ok(PreChargeCurrent::_120mA),
+            0x02 => Ok(PreChargeCurrent::_180mA),
+            0x03 => Ok(PreChargeCurrent::_240mA),
+            0x04 => Ok(PreChargeCurrent::_300mA),
+            0x05 => Ok(PreChargeCurrent::_360mA),
+            0x06 => Ok(PreChargeCurrent::_420mA),
+            0x07 => Ok(PreChargeCurrent::_480mA),
+            0x08 => Ok(PreChargeCurrent::_540mA),
+            0x09 => Ok(PreChargeCurrent::_600mA),
+            0x0a => Ok(PreChargeCurrent::_660mA),
+            0x0b => Ok(PreChargeCurrent::_720mA),
+            0x0c => Ok(PreChargeCurrent::_780mA),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -704,26 +894,28 @@ pub enum TermChargeCurrent {
 /// Converts `u8` to `TermChargeCurrent`
 ///
 /// Range: 0x00 - 0x0e (60 - 900 mA)
-/// Values above 0x0e (900mA) are clamped to 0x0e (900mA)
-impl From<u8> for TermChargeCurrent {
-    fn from(value: u8) -> Self {
+/// Values above 0x0e (900mA) are rejected
+impl TryFrom<u8> for TermChargeCurrent {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => TermChargeCurrent::_60mA,
-            0x01 => TermChargeCurrent::_120mA,
-            0x02 => TermChargeCurrent::_180mA,
-            0x03 => TermChargeCurrent::_240mA,
-            0x04 => TermChargeCurrent::_300mA,
-            0x05 => TermChargeCurrent::_360mA,
-            0x06 => TermChargeCurrent::_420mA,
-            0x07 => TermChargeCurrent::_480mA,
-            0x08 => TermChargeCurrent::_540mA,
-            0x09 => TermChargeCurrent::_600mA,
-            0x0a => TermChargeCurrent::_660mA,
-            0x0b => TermChargeCurrent::_720mA,
-            0x0c => TermChargeCurrent::_780mA,
-            0x0d => TermChargeCurrent::_840mA,
-            0x0e => TermChargeCurrent::_900mA,
-            _ => TermChargeCurrent::_900mA,
+            0x00 => Ok(TermChargeCurrent::_60mA),
+            0x01 => Ok(TermChargeCurrent::_120mA),
+            0x02 => Ok(TermChargeCurrent::_180mA),
+            0x03 => Ok(TermChargeCurrent::_240mA),
+            0x04 => Ok(TermChargeCurrent::_300mA),
+            0x05 => Ok(TermChargeCurrent::_360mA),
+            0x06 => Ok(TermChargeCurrent::_420mA),
+            0x07 => Ok(TermChargeCurrent::_480mA),
+            0x08 => Ok(TermChargeCurrent::_540mA),
+            0x09 => Ok(TermChargeCurrent::_600mA),
+            0x0a => Ok(TermChargeCurrent::_660mA),
+            0x0b => Ok(TermChargeCurrent::_720mA),
+            0x0c => Ok(TermChargeCurrent::_780mA),
+            0x0d => Ok(TermChargeCurrent::_840mA),
+            0x0e => Ok(TermChargeCurrent::_900mA),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -735,26 +927,50 @@ pub struct Reg03Values {
     pub term_charge_current: TermChargeCurrent,
 }
 
-/// Converts an `u8` to `Reg03Values`
+impl Reg03Values {
+    /// Power-on reset value (0x22).
+    pub const RESET: Self = Reg03Values {
+        pre_charge_current: PreChargeCurrent::_180mA,
+        term_charge_current: TermChargeCurrent::_180mA,
+    };
+}
+
+/// Converts `u8` to `Reg03Values`
 ///
 /// # Examples
 ///
 /// ```rust
 /// use sgm41511::types::{PreChargeCurrent, TermChargeCurrent, Reg03Values};
-/// let values = Reg03Values::from(0b00100010);
+/// let values = Reg03Values::try_from(0b00100010).unwrap();
 /// assert_eq!(values, Reg03Values { pre_charge_current: PreChargeCurrent::_180mA, term_charge_current: TermChargeCurrent::_180mA });
-impl From<u8> for Reg03Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg03Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg03Values {
+            pre_charge_current: PreChargeCurrent::try_from(value >> 4)
+                .map_err(|_| InvalidValue(value))?,
+            term_charge_current: TermChargeCurrent::try_from(value & 0x0f)
+                .map_err(|_| InvalidValue(value))?,
+        })
+    }
+}
+
+/// IPRECHG codes above 0x0c decode as 780 mA, ITERM code 0x0f as 900 mA.
+impl DecodeLenient for Reg03Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg03Values {
-            pre_charge_current: PreChargeCurrent::from(value >> 4),
-            term_charge_current: TermChargeCurrent::from(value & 0x0f),
+            pre_charge_current: PreChargeCurrent::try_from(value >> 4)
+                .unwrap_or(PreChargeCurrent::_780mA),
+            term_charge_current: TermChargeCurrent::try_from(value & 0x0f)
+                .unwrap_or(TermChargeCurrent::_900mA),
         }
     }
 }
 
-impl Into<u8> for Reg03Values {
-    fn into(self) -> u8 {
-        (self.pre_charge_current as u8) << 4 | (self.term_charge_current as u8)
+impl From<Reg03Values> for u8 {
+    fn from(values: Reg03Values) -> Self {
+        (values.pre_charge_current as u8) << 4 | (values.term_charge_current as u8)
     }
 }
 
@@ -818,36 +1034,38 @@ pub enum ChargeVoltageLimit {
 ///
 /// Range: 0x00 - 0x18 (3.856 - 4.624 V)
 /// Step: 32 mV
-/// Values above 0x18 (4.624 V) are clamped to 0x18 (4.624 V)
-impl From<u8> for ChargeVoltageLimit {
-    fn from(value: u8) -> Self {
+/// Values above 0x18 (4.624 V) are rejected
+impl TryFrom<u8> for ChargeVoltageLimit {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => ChargeVoltageLimit::_3_856V,
-            0x01 => ChargeVoltageLimit::_3_888V,
-            0x02 => ChargeVoltageLimit::_3_920V,
-            0x03 => ChargeVoltageLimit::_3_952V,
-            0x04 => ChargeVoltageLimit::_3_984V,
-            0x05 => ChargeVoltageLimit::_4_016V,
-            0x06 => ChargeVoltageLimit::_4_048V,
-            0x07 => ChargeVoltageLimit::_4_080V,
-            0x08 => ChargeVoltageLimit::_4_112V,
-            0x09 => ChargeVoltageLimit::_4_144V,
-            0x0a => ChargeVoltageLimit::_4_176V,
-            0x0b => ChargeVoltageLimit::_4_208V,
-            0x0c => ChargeVoltageLimit::_4_240V,
-            0x0d => ChargeVoltageLimit::_4_272V,
-            0x0e => ChargeVoltageLimit::_4_304V,
-            0x0f => ChargeVoltageLimit::_4_352V,
-            0x10 => ChargeVoltageLimit::_4_368V,
-            0x11 => ChargeVoltageLimit::_4_400V,
-            0x12 => ChargeVoltageLimit::_4_432V,
-            0x13 => ChargeVoltageLimit::_4_464V,
-            0x14 => ChargeVoltageLimit::_4_496V,
-            0x15 => ChargeVoltageLimit::_4_528V,
-            0x16 => ChargeVoltageLimit::_4_560V,
-            0x17 => ChargeVoltageLimit::_4_592V,
-            0x18 => ChargeVoltageLimit::_4_624V,
-            _ => ChargeVoltageLimit::_4_624V,
+            0x00 => Ok(ChargeVoltageLimit::_3_856V),
+            0x01 => Ok(ChargeVoltageLimit::_3_888V),
+            0x02 => Ok(ChargeVoltageLimit::_3_920V),
+            0x03 => Ok(ChargeVoltageLimit::_3_952V),
+            0x04 => Ok(ChargeVoltageLimit::_3_984V),
+            0x05 => Ok(ChargeVoltageLimit::_4_016V),
+            0x06 => Ok(ChargeVoltageLimit::_4_048V),
+            0x07 => Ok(ChargeVoltageLimit::_4_080V),
+            0x08 => Ok(ChargeVoltageLimit::_4_112V),
+            0x09 => Ok(ChargeVoltageLimit::_4_144V),
+            0x0a => Ok(ChargeVoltageLimit::_4_176V),
+            0x0b => Ok(ChargeVoltageLimit::_4_208V),
+            0x0c => Ok(ChargeVoltageLimit::_4_240V),
+            0x0d => Ok(ChargeVoltageLimit::_4_272V),
+            0x0e => Ok(ChargeVoltageLimit::_4_304V),
+            0x0f => Ok(ChargeVoltageLimit::_4_352V),
+            0x10 => Ok(ChargeVoltageLimit::_4_368V),
+            0x11 => Ok(ChargeVoltageLimit::_4_400V),
+            0x12 => Ok(ChargeVoltageLimit::_4_432V),
+            0x13 => Ok(ChargeVoltageLimit::_4_464V),
+            0x14 => Ok(ChargeVoltageLimit::_4_496V),
+            0x15 => Ok(ChargeVoltageLimit::_4_528V),
+            0x16 => Ok(ChargeVoltageLimit::_4_560V),
+            0x17 => Ok(ChargeVoltageLimit::_4_592V),
+            0x18 => Ok(ChargeVoltageLimit::_4_624V),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -869,14 +1087,16 @@ pub enum TopOffTimer {
 /// Converts `u8` to `TopOffTimer`
 ///
 /// Range: 0x00 - 0x03
-impl From<u8> for TopOffTimer {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for TopOffTimer {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => TopOffTimer::Disabled,
-            0x01 => TopOffTimer::_15Minutes,
-            0x02 => TopOffTimer::_30Minutes,
-            0x03 => TopOffTimer::_45Minutes,
-            _ => unreachable!("Invalid value for TopOffTimer. Range: 0x00 - 0x03"),
+            0x00 => Ok(TopOffTimer::Disabled),
+            0x01 => Ok(TopOffTimer::_15Minutes),
+            0x02 => Ok(TopOffTimer::_30Minutes),
+            0x03 => Ok(TopOffTimer::_45Minutes),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -894,12 +1114,14 @@ pub enum BatteryRechargeThreshold {
 /// Converts `u8` to `BatteryRechargeThreshold`
 ///
 /// Range: 0x00 - 0x01
-impl From<u8> for BatteryRechargeThreshold {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for BatteryRechargeThreshold {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => BatteryRechargeThreshold::_100mV,
-            0x01 => BatteryRechargeThreshold::_200mV,
-            _ => unreachable!("Invalid value for BatteryRechargeThreshold. Range: 0x00 - 0x01"),
+            0x00 => Ok(BatteryRechargeThreshold::_100mV),
+            0x01 => Ok(BatteryRechargeThreshold::_200mV),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -912,6 +1134,15 @@ pub struct Reg04Values {
     pub battery_recharge_threshold: BatteryRechargeThreshold,
 }
 
+impl Reg04Values {
+    /// Power-on reset value (0x58).
+    pub const RESET: Self = Reg04Values {
+        charge_voltage_limit: ChargeVoltageLimit::_4_208V,
+        top_off_timer: TopOffTimer::Disabled,
+        battery_recharge_threshold: BatteryRechargeThreshold::_100mV,
+    };
+}
+
 /// Converts `u8` to `Reg04Values`
 ///
 /// Example:
@@ -931,12 +1162,12 @@ pub struct Reg04Values {
 /// let values: u8 = Reg04Values { charge_voltage_limit: ChargeVoltageLimit::_4_352V, top_off_timer: TopOffTimer::Disabled, battery_recharge_threshold: BatteryRechargeThreshold::_100mV }.into();
 /// assert_eq!(values, 0b01111000);
 /// ```
-impl Into<u8> for Reg04Values {
-    fn into(self) -> u8 {
+impl From<Reg04Values> for u8 {
+    fn from(values: Reg04Values) -> Self {
         let mut value = 0u8;
-        value |= (self.charge_voltage_limit as u8) << 3;
-        value |= (self.top_off_timer as u8) << 1;
-        value |= self.battery_recharge_threshold as u8;
+        value |= (values.charge_voltage_limit as u8) << 3;
+        value |= (values.top_off_timer as u8) << 1;
+        value |= values.battery_recharge_threshold as u8;
         value
     }
 }
@@ -960,12 +1191,29 @@ impl Into<u8> for Reg04Values {
 /// let values: u8 = Reg04Values { charge_voltage_limit: ChargeVoltageLimit::_4_352V, top_off_timer: TopOffTimer::Disabled, battery_recharge_threshold: BatteryRechargeThreshold::_100mV }.into();
 /// assert_eq!(values, 0b01111000);
 /// ```
-impl From<u8> for Reg04Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg04Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let charge_voltage_limit =
+            ChargeVoltageLimit::try_from((value & 0xf8) >> 3).map_err(|_| InvalidValue(value))?;
+        Ok(Reg04Values {
+            charge_voltage_limit,
+            ..Reg04Values::decode_lenient(value)
+        })
+    }
+}
+
+/// VREG codes above 0x18 decode as 4.624 V.
+impl DecodeLenient for Reg04Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg04Values {
-            charge_voltage_limit: ChargeVoltageLimit::from((value & 0xf8) >> 3),
-            top_off_timer: TopOffTimer::from((value & 0x02) >> 1),
-            battery_recharge_threshold: BatteryRechargeThreshold::from(value & 0x01),
+            charge_voltage_limit: ChargeVoltageLimit::try_from((value & 0xf8) >> 3)
+                .unwrap_or(ChargeVoltageLimit::_4_624V),
+            top_off_timer: TopOffTimer::try_from((value & 0x06) >> 1)
+                .unwrap_or(TopOffTimer::_45Minutes),
+            battery_recharge_threshold: BatteryRechargeThreshold::try_from(value & 0x01)
+                .unwrap_or(BatteryRechargeThreshold::_200mV),
         }
     }
 }
@@ -983,21 +1231,23 @@ pub enum WatchDogTimerSetting {
 /// Converts `u8` to `WatchDogTimerSetting`
 ///
 /// Range: 0x00 - 0x03
-impl From<u8> for WatchDogTimerSetting {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for WatchDogTimerSetting {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => WatchDogTimerSetting::Disabled,
-            0x01 => WatchDogTimerSetting::_40Seconds,
-            0x02 => WatchDogTimerSetting::_80Seconds,
-            0x03 => WatchDogTimerSetting::_160Seconds,
-            _ => unreachable!("Invalid value for WatchDogTimerSetting. Range: 0x00 - 0x03"),
+            0x00 => Ok(WatchDogTimerSetting::Disabled),
+            0x01 => Ok(WatchDogTimerSetting::_40Seconds),
+            0x02 => Ok(WatchDogTimerSetting::_80Seconds),
+            0x03 => Ok(WatchDogTimerSetting::_160Seconds),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
 
-impl Into<u8> for WatchDogTimerSetting {
-    fn into(self) -> u8 {
-        self as u8
+impl From<WatchDogTimerSetting> for u8 {
+    fn from(value: WatchDogTimerSetting) -> Self {
+        value as u8
     }
 }
 
@@ -1012,12 +1262,14 @@ pub enum ChargeTimerSetting {
 /// Converts `u8` to `ChargeTimerSetting`
 ///
 /// Range: 0x00 - 0x01
-impl From<u8> for ChargeTimerSetting {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for ChargeTimerSetting {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => ChargeTimerSetting::_4Hours,
-            0x01 => ChargeTimerSetting::_6Hours,
-            _ => unreachable!("Invalid value for ChargeTimerSetting. Range: 0x00 - 0x01"),
+            0x00 => Ok(ChargeTimerSetting::_4Hours),
+            0x01 => Ok(ChargeTimerSetting::_6Hours),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1035,12 +1287,14 @@ pub enum ThermalRegulationThreshold {
 /// Converts `u8` to `ThermalRegulationThreshold`
 ///
 /// Range: 0x00 - 0x01
-impl From<u8> for ThermalRegulationThreshold {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for ThermalRegulationThreshold {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => ThermalRegulationThreshold::_80DegreeC,
-            0x01 => ThermalRegulationThreshold::_120DegreeC,
-            _ => unreachable!("Invalid value for ThermalRegulationThreshold. Range: 0x00 - 0x01"),
+            0x00 => Ok(ThermalRegulationThreshold::_80DegreeC),
+            0x01 => Ok(ThermalRegulationThreshold::_120DegreeC),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1058,12 +1312,14 @@ pub enum JEITAChargingCurrent {
 /// Converts `u8` to `JEITAChargingCurrent`
 ///
 /// Range: 0x00 - 0x01
-impl From<u8> for JEITAChargingCurrent {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for JEITAChargingCurrent {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => JEITAChargingCurrent::_50Percent,
-            0x01 => JEITAChargingCurrent::_20Percent,
-            _ => unreachable!("Invalid value for JEITAChargingCurrent. Range: 0x00 - 0x01"),
+            0x00 => Ok(JEITAChargingCurrent::_50Percent),
+            0x01 => Ok(JEITAChargingCurrent::_20Percent),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1079,13 +1335,25 @@ pub struct Reg05Values {
     pub jeita_charging_current: JEITAChargingCurrent,
 }
 
+impl Reg05Values {
+    /// Power-on reset value (0x9f).
+    pub const RESET: Self = Reg05Values {
+        term_enabled: true,
+        watchdog_timer_setting: WatchDogTimerSetting::_40Seconds,
+        timer_enabled: true,
+        charge_timer_setting: ChargeTimerSetting::_6Hours,
+        thermal_regulation_threshold: ThermalRegulationThreshold::_120DegreeC,
+        jeita_charging_current: JEITAChargingCurrent::_20Percent,
+    };
+}
+
 /// Converts `u8` to `Reg05Values`
 ///
 /// # Examples
 ///
 /// ```rust
 /// use sgm41511::types::*;
-/// let values = Reg05Values::from(0b10011111);
+/// let values = Reg05Values::try_from(0b10011111).unwrap();
 /// assert_eq!(values, Reg05Values {
 ///     term_enabled: true,
 ///     watchdog_timer_setting: WatchDogTimerSetting::_40Seconds,
@@ -1095,7 +1363,7 @@ pub struct Reg05Values {
 ///     jeita_charging_current: JEITAChargingCurrent::_20Percent,
 /// });
 ///
-/// let values = Reg05Values::from(0b00000000);
+/// let values = Reg05Values::try_from(0b00000000).unwrap();
 /// assert_eq!(values, Reg05Values {
 ///     term_enabled: false,
 ///     watchdog_timer_setting: WatchDogTimerSetting::Disabled,
@@ -1105,7 +1373,7 @@ pub struct Reg05Values {
 ///     jeita_charging_current: JEITAChargingCurrent::_50Percent,
 /// });
 ///
-/// let values = Reg05Values::from(0b11111111);
+/// let values = Reg05Values::try_from(0b11111111).unwrap();
 /// assert_eq!(values, Reg05Values {
 ///     term_enabled: true,
 ///     watchdog_timer_setting: WatchDogTimerSetting::_160Seconds,
@@ -1115,15 +1383,27 @@ pub struct Reg05Values {
 ///     jeita_charging_current: JEITAChargingCurrent::_20Percent,
 /// });
 /// ```
-impl From<u8> for Reg05Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg05Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg05Values::decode_lenient(value))
+    }
+}
+
+impl DecodeLenient for Reg05Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg05Values {
             term_enabled: value & 0x80 != 0,
-            watchdog_timer_setting: WatchDogTimerSetting::from((value & 0x30) >> 4),
-            timer_enabled: value & 0x30 != 0,
-            charge_timer_setting: ChargeTimerSetting::from((value & 0x04) >> 2),
-            thermal_regulation_threshold: ThermalRegulationThreshold::from((value & 0x02) >> 1),
-            jeita_charging_current: JEITAChargingCurrent::from(value & 0x01),
+            watchdog_timer_setting: WatchDogTimerSetting::try_from((value & 0x30) >> 4)
+                .unwrap_or(WatchDogTimerSetting::_160Seconds),
+            timer_enabled: value & 0x08 != 0,
+            charge_timer_setting: ChargeTimerSetting::try_from((value & 0x04) >> 2)
+                .unwrap_or(ChargeTimerSetting::_6Hours),
+            thermal_regulation_threshold: ThermalRegulationThreshold::try_from((value & 0x02) >> 1)
+                .unwrap_or(ThermalRegulationThreshold::_120DegreeC),
+            jeita_charging_current: JEITAChargingCurrent::try_from(value & 0x01)
+                .unwrap_or(JEITAChargingCurrent::_20Percent),
         }
     }
 }
@@ -1164,19 +1444,19 @@ impl From<u8> for Reg05Values {
 /// }.into();
 /// assert_eq!(values, 0b10111111);
 /// ```
-impl<'a> Into<u8> for Reg05Values {
-    fn into(self) -> u8 {
+impl From<Reg05Values> for u8 {
+    fn from(values: Reg05Values) -> Self {
         let mut value = 0u8;
-        if self.term_enabled {
+        if values.term_enabled {
             value |= 0x80;
         }
-        value |= (self.watchdog_timer_setting as u8) << 4;
-        if self.timer_enabled {
+        value |= (values.watchdog_timer_setting as u8) << 4;
+        if values.timer_enabled {
             value |= 0x08;
         }
-        value |= (self.charge_timer_setting as u8) << 2;
-        value |= (self.thermal_regulation_threshold as u8) << 1;
-        value |= self.jeita_charging_current as u8;
+        value |= (values.charge_timer_setting as u8) << 2;
+        value |= (values.thermal_regulation_threshold as u8) << 1;
+        value |= values.jeita_charging_current as u8;
         value
     }
 }
@@ -1198,14 +1478,16 @@ pub enum OVPThreshold {
 /// Converts `OVPThreshold` to `u8`
 ///
 /// Range: 0x00 - 0x03
-impl From<u8> for OVPThreshold {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for OVPThreshold {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => OVPThreshold::_5_5V,
-            0x01 => OVPThreshold::_6_5V,
-            0x02 => OVPThreshold::_10_5V,
-            0x03 => OVPThreshold::_14V,
-            _ => unreachable!(),
+            0x00 => Ok(OVPThreshold::_5_5V),
+            0x01 => Ok(OVPThreshold::_6_5V),
+            0x02 => Ok(OVPThreshold::_10_5V),
+            0x03 => Ok(OVPThreshold::_14V),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1227,14 +1509,16 @@ pub enum BoostModeVoltage {
 /// Converts `BoostModeVoltage` to `u8`
 ///
 /// Range: 0x00 - 0x03
-impl From<u8> for BoostModeVoltage {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for BoostModeVoltage {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => BoostModeVoltage::_4_85V,
-            0x01 => BoostModeVoltage::_5_00V,
-            0x02 => BoostModeVoltage::_5_15V,
-            0x03 => BoostModeVoltage::_5_30V,
-            _ => unreachable!(),
+            0x00 => Ok(BoostModeVoltage::_4_85V),
+            0x01 => Ok(BoostModeVoltage::_5_00V),
+            0x02 => Ok(BoostModeVoltage::_5_15V),
+            0x03 => Ok(BoostModeVoltage::_5_30V),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1280,26 +1564,28 @@ pub enum VINDPMThreshold {
 /// Converts `VINDPMThreshold` to `u8`
 ///
 /// Range: 0x00 - 0x0f
-impl From<u8> for VINDPMThreshold {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for VINDPMThreshold {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => VINDPMThreshold::_3_9V,
-            0x01 => VINDPMThreshold::_4_0V,
-            0x02 => VINDPMThreshold::_4_1V,
-            0x03 => VINDPMThreshold::_4_2V,
-            0x04 => VINDPMThreshold::_4_3V,
-            0x05 => VINDPMThreshold::_4_4V,
-            0x06 => VINDPMThreshold::_4_5V,
-            0x07 => VINDPMThreshold::_4_6V,
-            0x08 => VINDPMThreshold::_4_7V,
-            0x09 => VINDPMThreshold::_4_8V,
-            0x0a => VINDPMThreshold::_4_9V,
-            0x0b => VINDPMThreshold::_5_0V,
-            0x0c => VINDPMThreshold::_5_1V,
-            0x0d => VINDPMThreshold::_5_2V,
-            0x0e => VINDPMThreshold::_5_3V,
-            0x0f => VINDPMThreshold::_5_4V,
-            _ => unreachable!(),
+            0x00 => Ok(VINDPMThreshold::_3_9V),
+            0x01 => Ok(VINDPMThreshold::_4_0V),
+            0x02 => Ok(VINDPMThreshold::_4_1V),
+            0x03 => Ok(VINDPMThreshold::_4_2V),
+            0x04 => Ok(VINDPMThreshold::_4_3V),
+            0x05 => Ok(VINDPMThreshold::_4_4V),
+            0x06 => Ok(VINDPMThreshold::_4_5V),
+            0x07 => Ok(VINDPMThreshold::_4_6V),
+            0x08 => Ok(VINDPMThreshold::_4_7V),
+            0x09 => Ok(VINDPMThreshold::_4_8V),
+            0x0a => Ok(VINDPMThreshold::_4_9V),
+            0x0b => Ok(VINDPMThreshold::_5_0V),
+            0x0c => Ok(VINDPMThreshold::_5_1V),
+            0x0d => Ok(VINDPMThreshold::_5_2V),
+            0x0e => Ok(VINDPMThreshold::_5_3V),
+            0x0f => Ok(VINDPMThreshold::_5_4V),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1313,6 +1599,15 @@ pub struct Reg06Values {
     pub vindpm_threshold: VINDPMThreshold,
 }
 
+impl Reg06Values {
+    /// Power-on reset value (0x66).
+    pub const RESET: Self = Reg06Values {
+        ovp_threshold: OVPThreshold::_6_5V,
+        boost_mode_voltage: BoostModeVoltage::_5_15V,
+        vindpm_threshold: VINDPMThreshold::_4_5V,
+    };
+}
+
 /// Converts `u8` to `Reg06Values`
 ///
 /// Examples
@@ -1320,21 +1615,32 @@ pub struct Reg06Values {
 /// ```rust
 /// use sgm41511::types::*;
 ///
-/// let values = Reg06Values::from(0b01100110);
+/// let values = Reg06Values::try_from(0b01100110).unwrap();
 /// assert_eq!(values, Reg06Values { ovp_threshold: OVPThreshold::_6_5V, boost_mode_voltage: BoostModeVoltage::_5_15V, vindpm_threshold: VINDPMThreshold::_4_5V });
 ///
-/// let values = Reg06Values::from(0b00000000);
+/// let values = Reg06Values::try_from(0b00000000).unwrap();
 /// assert_eq!(values, Reg06Values { ovp_threshold: OVPThreshold::_5_5V, boost_mode_voltage: BoostModeVoltage::_4_85V, vindpm_threshold: VINDPMThreshold::_3_9V });
 ///
-/// let values = Reg06Values::from(0b11111111);
+/// let values = Reg06Values::try_from(0b11111111).unwrap();
 /// assert_eq!(values, Reg06Values { ovp_threshold: OVPThreshold::_14V, boost_mode_voltage: BoostModeVoltage::_5_30V, vindpm_threshold: VINDPMThreshold::_5_4V });
 /// ```
-impl From<u8> for Reg06Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg06Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg06Values::decode_lenient(value))
+    }
+}
+
+impl DecodeLenient for Reg06Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg06Values {
-            ovp_threshold: OVPThreshold::from((value & 0xc0) >> 6),
-            boost_mode_voltage: BoostModeVoltage::from((value & 0x30) >> 4),
-            vindpm_threshold: VINDPMThreshold::from((value & 0x0f) & 0x0f),
+            ovp_threshold: OVPThreshold::try_from((value & 0xc0) >> 6)
+                .unwrap_or(OVPThreshold::_14V),
+            boost_mode_voltage: BoostModeVoltage::try_from((value & 0x30) >> 4)
+                .unwrap_or(BoostModeVoltage::_5_30V),
+            vindpm_threshold: VINDPMThreshold::try_from(value & 0x0f)
+                .unwrap_or(VINDPMThreshold::_5_4V),
         }
     }
 }
@@ -1354,12 +1660,12 @@ impl From<u8> for Reg06Values {
 /// let value: u8 = Reg06Values { ovp_threshold: OVPThreshold::_14V, boost_mode_voltage: BoostModeVoltage::_5_30V, vindpm_threshold: VINDPMThreshold::_5_4V }.into();
 /// assert_eq!(value, 0b11111111);
 /// ```
-impl Into<u8> for Reg06Values {
-    fn into(self) -> u8 {
+impl From<Reg06Values> for u8 {
+    fn from(values: Reg06Values) -> Self {
         let mut value = 0u8;
-        value |= (self.ovp_threshold as u8) << 6;
-        value |= (self.boost_mode_voltage as u8) << 4;
-        value |= (self.vindpm_threshold as u8) << 0;
+        value |= (values.ovp_threshold as u8) << 6;
+        value |= (values.boost_mode_voltage as u8) << 4;
+        value |= values.vindpm_threshold as u8;
         value
     }
 }
@@ -1375,12 +1681,14 @@ pub enum JEITAVoltageSetting {
 /// Converts `u8` to `JEITAVoltageSetting`
 ///
 /// Range: 0x00 - 0x01
-impl From<u8> for JEITAVoltageSetting {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for JEITAVoltageSetting {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => JEITAVoltageSetting::LowerOf4_1V,
-            0x01 => JEITAVoltageSetting::VReg,
-            _ => unreachable!(),
+            0x00 => Ok(JEITAVoltageSetting::LowerOf4_1V),
+            0x01 => Ok(JEITAVoltageSetting::VReg),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1402,14 +1710,16 @@ pub enum VDPMBatteryVoltageTracking {
 /// Converts `u8` to `VDPMBatteryVoltageTracking`
 ///
 /// Range: 0x00 - 0x03
-impl From<u8> for VDPMBatteryVoltageTracking {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for VDPMBatteryVoltageTracking {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => VDPMBatteryVoltageTracking::Disabled,
-            0x01 => VDPMBatteryVoltageTracking::_200mV,
-            0x02 => VDPMBatteryVoltageTracking::_250mV,
-            0x03 => VDPMBatteryVoltageTracking::_300mV,
-            _ => unreachable!(),
+            0x00 => Ok(VDPMBatteryVoltageTracking::Disabled),
+            0x01 => Ok(VDPMBatteryVoltageTracking::_200mV),
+            0x02 => Ok(VDPMBatteryVoltageTracking::_250mV),
+            0x03 => Ok(VDPMBatteryVoltageTracking::_300mV),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1433,6 +1743,19 @@ pub struct Reg07Values {
     pub vdpm_battery_tracking: VDPMBatteryVoltageTracking,
 }
 
+impl Reg07Values {
+    /// Power-on reset value (0x4c).
+    pub const RESET: Self = Reg07Values {
+        iindet_enabled: false,
+        tmr2x_enabled: true,
+        batfet_disabled: false,
+        jeita_voltage_setting: JEITAVoltageSetting::LowerOf4_1V,
+        batfet_delay: true,
+        batfet_reset_enabled: true,
+        vdpm_battery_tracking: VDPMBatteryVoltageTracking::Disabled,
+    };
+}
+
 /// Converts `u8` to `Reg07Values`
 ///
 /// # Examples
@@ -1440,7 +1763,7 @@ pub struct Reg07Values {
 /// ```rust
 /// use sgm41511::types::*;
 ///
-/// let values = Reg07Values::from(0b01001100);
+/// let values = Reg07Values::try_from(0b01001100).unwrap();
 /// assert_eq!(values, Reg07Values {
 ///     iindet_enabled: false,
 ///     tmr2x_enabled: true,
@@ -1451,7 +1774,7 @@ pub struct Reg07Values {
 ///     vdpm_battery_tracking: VDPMBatteryVoltageTracking::Disabled,
 /// });
 ///
-/// let values = Reg07Values::from(0b00000000);
+/// let values = Reg07Values::try_from(0b00000000).unwrap();
 /// assert_eq!(values, Reg07Values {
 ///     iindet_enabled: false,
 ///     tmr2x_enabled: false,
@@ -1462,7 +1785,7 @@ pub struct Reg07Values {
 ///     vdpm_battery_tracking: VDPMBatteryVoltageTracking::Disabled,
 /// });
 ///
-/// let values = Reg07Values::from(0b11111111);
+/// let values = Reg07Values::try_from(0b11111111).unwrap();
 /// assert_eq!(values, Reg07Values {
 ///     iindet_enabled: true,
 ///     tmr2x_enabled: true,
@@ -1473,16 +1796,26 @@ pub struct Reg07Values {
 ///     vdpm_battery_tracking: VDPMBatteryVoltageTracking::_300mV,
 /// });
 /// ```
-impl From<u8> for Reg07Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg07Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg07Values::decode_lenient(value))
+    }
+}
+
+impl DecodeLenient for Reg07Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg07Values {
             iindet_enabled: (value & 0b10000000) != 0,
             tmr2x_enabled: (value & 0b01000000) != 0,
             batfet_disabled: (value & 0b00100000) != 0,
-            jeita_voltage_setting: JEITAVoltageSetting::from((value & 0b00010000) >> 4),
+            jeita_voltage_setting: JEITAVoltageSetting::try_from((value & 0b00010000) >> 4)
+                .unwrap_or(JEITAVoltageSetting::VReg),
             batfet_delay: (value & 0b00001000) != 0,
             batfet_reset_enabled: (value & 0b00000100) != 0,
-            vdpm_battery_tracking: VDPMBatteryVoltageTracking::from((value & 0b00000011) as u8),
+            vdpm_battery_tracking: VDPMBatteryVoltageTracking::try_from(value & 0b00000011)
+                .unwrap_or(VDPMBatteryVoltageTracking::_300mV),
         }
     }
 }
@@ -1527,26 +1860,26 @@ impl From<u8> for Reg07Values {
 /// }.into();
 /// assert_eq!(values, 0b11111111);
 /// ```
-impl Into<u8> for Reg07Values {
-    fn into(self) -> u8 {
+impl From<Reg07Values> for u8 {
+    fn from(values: Reg07Values) -> Self {
         let mut value = 0u8;
-        if self.iindet_enabled {
+        if values.iindet_enabled {
             value |= 0b10000000;
         }
-        if self.tmr2x_enabled {
+        if values.tmr2x_enabled {
             value |= 0b01000000;
         }
-        if self.batfet_disabled {
+        if values.batfet_disabled {
             value |= 0b00100000;
         }
-        value |= (self.jeita_voltage_setting as u8) << 4;
-        if self.batfet_delay {
+        value |= (values.jeita_voltage_setting as u8) << 4;
+        if values.batfet_delay {
             value |= 0b00001000;
         }
-        if self.batfet_reset_enabled {
+        if values.batfet_reset_enabled {
             value |= 0b00000100;
         }
-        value |= self.vdpm_battery_tracking as u8;
+        value |= values.vdpm_battery_tracking as u8;
         value
     }
 }
@@ -1556,22 +1889,65 @@ impl Into<u8> for Reg07Values {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum VBUSStatus {
     NoInput = 0x00,
+    // USB Host SDP (500 mA)
     USBHostSDP = 0x01,
-    Adaptor2_4A = 0x02,
-    OTG = 0x03,
+    // USB CDP (1.5 A)
+    USBCDP = 0x02,
+    // USB DCP (2.4 A)
+    USBDCP = 0x03,
+    // Unknown Adapter (500 mA)
+    UnknownAdapter = 0x04,
+    // Non-Standard Adapter (1 A / 2 A / 2.1 A / 2.4 A)
+    NonStandardAdapter = 0x05,
+    Reserved = 0x06,
+    OTG = 0x07,
 }
 
 /// Converts u8 to `VBUSStatus`
 ///
-/// Range 0x00 - 0x03
-impl From<u8> for VBUSStatus {
-    fn from(value: u8) -> Self {
+/// Range 0x00 - 0x07
+impl TryFrom<u8> for VBUSStatus {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => VBUSStatus::NoInput,
-            0x01 => VBUSStatus::USBHostSDP,
-            0x02 => VBUSStatus::Adaptor2_4A,
-            0x03 => VBUSStatus::OTG,
-            _ => unreachable!(),
+            0x00 => Ok(VBUSStatus::NoInput),
+            0x01 => Ok(VBUSStatus::USBHostSDP),
+            0x02 => Ok(VBUSStatus::USBCDP),
+            0x03 => Ok(VBUSStatus::USBDCP),
+            0x04 => Ok(VBUSStatus::UnknownAdapter),
+            0x05 => Ok(VBUSStatus::NonStandardAdapter),
+            0x06 => Ok(VBUSStatus::Reserved),
+            0x07 => Ok(VBUSStatus::OTG),
+            _ => Err(InvalidValue(value)),
+        }
+    }
+}
+
+impl VBUSStatus {
+    /// Returns the input current limit suited to the detected input source,
+    /// or `None` when there is no source to draw from.
+    ///
+    /// Depending on their D+/D- levels, the SGM41511 sets 1 A, 2 A, 2.1 A or
+    /// 2.4 A for non-standard adapters. REG08 does not tell which, so they
+    /// get 1 A, the lowest of these.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sgm41511::types::*;
+    /// assert_eq!(VBUSStatus::USBDCP.recommended_input_current_limit(), Some(InputCurrentLimit::_2400mA));
+    /// assert_eq!(VBUSStatus::NonStandardAdapter.recommended_input_current_limit(), Some(InputCurrentLimit::_1000mA));
+    /// assert_eq!(VBUSStatus::OTG.recommended_input_current_limit(), None);
+    /// ```
+    pub fn recommended_input_current_limit(&self) -> Option<InputCurrentLimit> {
+        match self {
+            VBUSStatus::USBHostSDP => Some(InputCurrentLimit::_500mA),
+            VBUSStatus::USBCDP => Some(InputCurrentLimit::_1500mA),
+            VBUSStatus::USBDCP => Some(InputCurrentLimit::_2400mA),
+            VBUSStatus::UnknownAdapter => Some(InputCurrentLimit::_500mA),
+            VBUSStatus::NonStandardAdapter => Some(InputCurrentLimit::_1000mA),
+            VBUSStatus::NoInput | VBUSStatus::Reserved | VBUSStatus::OTG => None,
         }
     }
 }
@@ -1589,14 +1965,16 @@ pub enum ChargeStatus {
 /// Converts u8 to `ChargeStatus`
 ///
 /// Range 0x00 - 0x03
-impl From<u8> for ChargeStatus {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for ChargeStatus {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => ChargeStatus::Disabled,
-            0x01 => ChargeStatus::Pre,
-            0x02 => ChargeStatus::Fast,
-            0x03 => ChargeStatus::Terminated,
-            _ => unreachable!(),
+            0x00 => Ok(ChargeStatus::Disabled),
+            0x01 => Ok(ChargeStatus::Pre),
+            0x02 => Ok(ChargeStatus::Fast),
+            0x03 => Ok(ChargeStatus::Terminated),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1615,13 +1993,25 @@ pub struct Reg08Values {
     pub vsys_status: bool,
 }
 
+impl Reg08Values {
+    /// Value after power-on reset (0x00), before any input or fault
+    /// has been detected.
+    pub const RESET: Self = Reg08Values {
+        vbus_status: VBUSStatus::NoInput,
+        charge_status: ChargeStatus::Disabled,
+        pg_status: false,
+        therm_status: false,
+        vsys_status: false,
+    };
+}
+
 /// Converts `u8` to `Reg08Values`
 ///
 /// # Examples
 ///
 /// ```rust
 /// use sgm41511::types::*;
-/// let values = Reg08Values::from(0b00101010);
+/// let values = Reg08Values::try_from(0b00101010).unwrap();
 /// assert_eq!(values, Reg08Values {
 ///     vbus_status: VBUSStatus::USBHostSDP,
 ///     charge_status: ChargeStatus::Pre,
@@ -1630,20 +2020,32 @@ pub struct Reg08Values {
 ///     vsys_status: false,
 /// });
 ///
-/// let values = Reg08Values::from(0b01010101);
+/// let values = Reg08Values::try_from(0b01010101).unwrap();
 /// assert_eq!(values, Reg08Values {
-///     vbus_status: VBUSStatus::Adaptor2_4A,
+///     vbus_status: VBUSStatus::USBCDP,
 ///     charge_status: ChargeStatus::Fast,
 ///     pg_status: true,
 ///     therm_status: false,
 ///     vsys_status: true,
 /// });
+///
+/// let values = Reg08Values::try_from(0b11100000).unwrap();
+/// assert_eq!(values.vbus_status, VBUSStatus::OTG);
 /// ```
-impl From<u8> for Reg08Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg08Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg08Values::decode_lenient(value))
+    }
+}
+
+impl DecodeLenient for Reg08Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg08Values {
-            vbus_status: VBUSStatus::from((value & 0xe0) >> 5),
-            charge_status: ChargeStatus::from((value & 0x18) >> 3),
+            vbus_status: VBUSStatus::try_from((value & 0xe0) >> 5).unwrap_or(VBUSStatus::Reserved),
+            charge_status: ChargeStatus::try_from((value & 0x18) >> 3)
+                .unwrap_or(ChargeStatus::Terminated),
             pg_status: value & 0x04 != 0,
             therm_status: value & 0x02 != 0,
             vsys_status: value & 0x01 != 0,
@@ -1657,19 +2059,19 @@ impl From<u8> for Reg08Values {
 ///
 /// ```rust
 /// use sgm41511::types::*;
-/// let values: u8 = Reg08Values::from(0b00101010).into();
+/// let values: u8 = Reg08Values::try_from(0b00101010).unwrap().into();
 /// assert_eq!(values, 0b00101010);
 ///
-/// let values: u8 = Reg08Values::from(0b01010101).into();
+/// let values: u8 = Reg08Values::try_from(0b01010101).unwrap().into();
 /// assert_eq!(values, 0b01010101);
 /// ```
-impl Into<u8> for Reg08Values {
-    fn into(self) -> u8 {
-        (self.vbus_status as u8) << 5
-            | (self.charge_status as u8) << 3
-            | if self.pg_status { 0x04 } else { 0x00 }
-            | if self.therm_status { 0x02 } else { 0x00 }
-            | if self.vsys_status { 0x01 } else { 0x00 }
+impl From<Reg08Values> for u8 {
+    fn from(values: Reg08Values) -> Self {
+        (values.vbus_status as u8) << 5
+            | (values.charge_status as u8) << 3
+            | if values.pg_status { 0x04 } else { 0x00 }
+            | if values.therm_status { 0x02 } else { 0x00 }
+            | if values.vsys_status { 0x01 } else { 0x00 }
     }
 }
 
@@ -1686,14 +2088,16 @@ pub enum ChargeFault {
 /// Converts u8 to `ChargeFault`
 ///
 /// Range 0x00 - 0x03
-impl From<u8> for ChargeFault {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for ChargeFault {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => ChargeFault::Normal,
-            0x01 => ChargeFault::InputFault,
-            0x02 => ChargeFault::ThermalShutdown,
-            0x03 => ChargeFault::ChhargeSafetyTimerExpired,
-            _ => unreachable!(),
+            0x00 => Ok(ChargeFault::Normal),
+            0x01 => Ok(ChargeFault::InputFault),
+            0x02 => Ok(ChargeFault::ThermalShutdown),
+            0x03 => Ok(ChargeFault::ChhargeSafetyTimerExpired),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1712,16 +2116,18 @@ pub enum NtcFault {
 
 /// Converts u8 to `NtcFault`
 ///
-/// Range 0x00 - 0x04
-impl From<u8> for NtcFault {
-    fn from(value: u8) -> Self {
+/// Valid codes: 0x00, 0x02, 0x03, 0x05 and 0x06
+impl TryFrom<u8> for NtcFault {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0x00 => NtcFault::Normal,
-            0x02 => NtcFault::Warm,
-            0x03 => NtcFault::Cool,
-            0x05 => NtcFault::Cold,
-            0x06 => NtcFault::Hot,
-            _ => unreachable!(),
+            0x00 => Ok(NtcFault::Normal),
+            0x02 => Ok(NtcFault::Warm),
+            0x03 => Ok(NtcFault::Cool),
+            0x05 => Ok(NtcFault::Cold),
+            0x06 => Ok(NtcFault::Hot),
+            _ => Err(InvalidValue(value)),
         }
     }
 }
@@ -1747,13 +2153,25 @@ pub struct Reg09Values {
     pub ntc_fault: NtcFault,
 }
 
+impl Reg09Values {
+    /// Value after power-on reset (0x00), before any input or fault
+    /// has been detected.
+    pub const RESET: Self = Reg09Values {
+        watchdog_fault: false,
+        boost_fault: false,
+        charge_fault: ChargeFault::Normal,
+        bat_fault: false,
+        ntc_fault: NtcFault::Normal,
+    };
+}
+
 /// Converts `u8` to `Reg09Values`
 ///
 /// # Examples
 ///
 /// ```rust
 /// use sgm41511::types::*;
-/// let values = Reg09Values::from(0b11111110);
+/// let values = Reg09Values::try_from(0b11111110).unwrap();
 /// assert_eq!(values, Reg09Values {
 ///     watchdog_fault: true,
 ///     boost_fault: true,
@@ -1762,7 +2180,7 @@ pub struct Reg09Values {
 ///     ntc_fault: NtcFault::Hot,
 /// });
 ///
-/// let values = Reg09Values::from(0b00000000);
+/// let values = Reg09Values::try_from(0b00000000).unwrap();
 /// assert_eq!(values, Reg09Values {
 ///     watchdog_fault: false,
 ///     boost_fault: false,
@@ -1771,7 +2189,7 @@ pub struct Reg09Values {
 ///     ntc_fault: NtcFault::Normal,
 /// });
 ///
-/// let values = Reg09Values::from(0b10101010);
+/// let values = Reg09Values::try_from(0b10101010).unwrap();
 /// assert_eq!(values, Reg09Values {
 ///     watchdog_fault: true,
 ///     boost_fault: false,
@@ -1780,14 +2198,28 @@ pub struct Reg09Values {
 ///     ntc_fault: NtcFault::Warm,
 /// });
 /// ```
-impl From<u8> for Reg09Values {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg09Values {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let ntc_fault = NtcFault::try_from(value & 0x07).map_err(|_| InvalidValue(value))?;
+        Ok(Reg09Values {
+            ntc_fault,
+            ..Reg09Values::decode_lenient(value)
+        })
+    }
+}
+
+/// Reserved NTC_FAULT codes decode as `Normal`.
+impl DecodeLenient for Reg09Values {
+    fn decode_lenient(value: u8) -> Self {
         Reg09Values {
             watchdog_fault: value & 0x80 != 0,
             boost_fault: value & 0x40 != 0,
-            charge_fault: ChargeFault::from((value & 0x30) >> 4),
+            charge_fault: ChargeFault::try_from((value & 0x30) >> 4)
+                .unwrap_or(ChargeFault::ChhargeSafetyTimerExpired),
             bat_fault: value & 0x08 != 0,
-            ntc_fault: NtcFault::from(value & 0x07),
+            ntc_fault: NtcFault::try_from(value & 0x07).unwrap_or(NtcFault::Normal),
         }
     }
 }
@@ -1826,20 +2258,20 @@ impl From<u8> for Reg09Values {
 /// assert_eq!(values, 0b10101010);
 ///
 /// ```
-impl Into<u8> for Reg09Values {
-    fn into(self) -> u8 {
+impl From<Reg09Values> for u8 {
+    fn from(values: Reg09Values) -> Self {
         let mut value = 0u8;
-        if self.watchdog_fault {
+        if values.watchdog_fault {
             value |= 0x80;
         }
-        if self.boost_fault {
+        if values.boost_fault {
             value |= 0x40;
         }
-        value |= (self.charge_fault as u8) << 4;
-        if self.bat_fault {
+        value |= (values.charge_fault as u8) << 4;
+        if values.bat_fault {
             value |= 0x08;
         }
-        value |= self.ntc_fault as u8;
+        value |= values.ntc_fault as u8;
         value
     }
 }
@@ -1865,13 +2297,105 @@ pub struct Reg0aValues {
     pub iindpm_int_mask: bool,
 }
 
+impl Reg0aValues {
+    /// Value after power-on reset (0x00), before any input or fault
+    /// has been detected.
+    pub const RESET: Self = Reg0aValues {
+        vbus_gd: false,
+        vindpm_status: false,
+        iindpm_status: false,
+        iindet_enabled: false,
+        topoff_active: false,
+        acov_status: false,
+        vindpm_int_mask: false,
+        iindpm_int_mask: false,
+    };
+
+    /// Returns the VINDPM/IINDPM interrupt mask bits as an [`InterruptMask`].
+    pub fn interrupt_mask(&self) -> InterruptMask {
+        let mut mask = InterruptMask::NONE;
+        if self.vindpm_int_mask {
+            mask.insert(InterruptMask::VINDPM);
+        }
+        if self.iindpm_int_mask {
+            mask.insert(InterruptMask::IINDPM);
+        }
+        mask
+    }
+
+    /// Sets the VINDPM/IINDPM interrupt mask bits from an [`InterruptMask`].
+    pub fn set_interrupt_mask(&mut self, mask: InterruptMask) {
+        self.vindpm_int_mask = mask.contains(InterruptMask::VINDPM);
+        self.iindpm_int_mask = mask.contains(InterruptMask::IINDPM);
+    }
+}
+
+/// Set of masked INT sources, stored in bits 1:0 of REG0A.
+///
+/// A masked source no longer pulses the INT pin.
+///
+/// # Examples
+///
+/// ```rust
+/// use sgm41511::types::*;
+/// let mask = InterruptMask::VINDPM | InterruptMask::IINDPM;
+/// assert_eq!(mask, InterruptMask::ALL);
+/// assert!(mask.contains(InterruptMask::VINDPM));
+/// assert_eq!(mask.bits(), 0b11);
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InterruptMask(u8);
+
+impl InterruptMask {
+    pub const NONE: InterruptMask = InterruptMask(0x00);
+    /// IINDPM_INT_MASK
+    pub const IINDPM: InterruptMask = InterruptMask(0x01);
+    /// VINDPM_INT_MASK
+    pub const VINDPM: InterruptMask = InterruptMask(0x02);
+    pub const ALL: InterruptMask = InterruptMask(0x03);
+
+    /// Builds a mask from the REG0A bits, ignoring all other bits.
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        InterruptMask(bits & Self::ALL.0)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(&self, other: InterruptMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: InterruptMask) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: InterruptMask) {
+        self.0 &= !other.0;
+    }
+}
+
+impl core::ops::BitOr for InterruptMask {
+    type Output = InterruptMask;
+
+    fn bitor(self, rhs: InterruptMask) -> InterruptMask {
+        InterruptMask(self.0 | rhs.0)
+    }
+}
+
 /// Converts `u8` to `Reg0aValues`
 ///
 /// # Examples
 ///
 /// ```rust
 /// use sgm41511::types::*;
-/// let values = Reg0aValues::from(0b10101010);
+/// let values = Reg0aValues::try_from(0b10101010).unwrap();
 /// assert_eq!(values, Reg0aValues {
 ///     vbus_gd: true,
 ///     vindpm_status: false,
@@ -1883,7 +2407,7 @@ pub struct Reg0aValues {
 ///     iindpm_int_mask: false
 /// });
 ///
-/// let values = Reg0aValues::from(0b01010101);
+/// let values = Reg0aValues::try_from(0b01010101).unwrap();
 /// assert_eq!(values, Reg0aValues {
 ///     vbus_gd: false,
 ///     vindpm_status: true,
@@ -1895,8 +2419,16 @@ pub struct Reg0aValues {
 ///     iindpm_int_mask: true
 /// });
 /// ```
-impl From<u8> for Reg0aValues {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Reg0aValues {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg0aValues::decode_lenient(value))
+    }
+}
+
+impl DecodeLenient for Reg0aValues {
+    fn decode_lenient(value: u8) -> Self {
         Reg0aValues {
             vbus_gd: value & 0x80 != 0,
             vindpm_status: value & 0x40 != 0,
@@ -1940,33 +2472,191 @@ impl From<u8> for Reg0aValues {
 /// }.into();
 /// assert_eq!(values, 0b01010101);
 /// ```
-impl Into<u8> for Reg0aValues {
-    fn into(self) -> u8 {
+impl From<Reg0aValues> for u8 {
+    fn from(values: Reg0aValues) -> Self {
         let mut value = 0u8;
-        if self.vbus_gd {
+        if values.vbus_gd {
             value |= 0x80;
         }
-        if self.vindpm_status {
+        if values.vindpm_status {
             value |= 0x40;
         }
-        if self.iindpm_status {
+        if values.iindpm_status {
             value |= 0x20;
         }
-        if self.iindet_enabled {
+        if values.iindet_enabled {
             value |= 0x10;
         }
-        if self.topoff_active {
+        if values.topoff_active {
             value |= 0x08;
         }
-        if self.acov_status {
+        if values.acov_status {
             value |= 0x04;
         }
-        if self.vindpm_int_mask {
+        if values.vindpm_int_mask {
             value |= 0x02;
         }
-        if self.iindpm_int_mask {
+        if values.iindpm_int_mask {
             value |= 0x01;
         }
         value
     }
 }
+
+#[repr(u8)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PartNumber {
+    SGM41511 = 0b0010,
+}
+
+/// Converts `u8` to `PartNumber`
+///
+/// Only the PN code of the SGM41511 (0b0010) is accepted.
+impl TryFrom<u8> for PartNumber {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0b0010 => Ok(PartNumber::SGM41511),
+            _ => Err(InvalidValue(value)),
+        }
+    }
+}
+
+/// Part Information and Register Reset
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Reg0bValues {
+    /// Register Reset
+    ///
+    /// Writing `true` resets all registers to their default values. Reads
+    /// back as `false` once the reset has completed.
+    pub reg_reset: bool,
+    /// Part Number (PN), 4 bits
+    pub part_number: u8,
+    /// SGM Part Flag
+    pub sgm_part: bool,
+    /// Device Revision (DEV_REV), 2 bits
+    pub device_revision: u8,
+}
+
+/// Identification of the populated chip, read from REG0B.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceInfo {
+    pub part_number: PartNumber,
+    pub device_revision: u8,
+}
+
+impl Reg0bValues {
+    /// Value after power-on reset (0x14). The part number and revision
+    /// bits are fixed by the chip, revision 0 is assumed.
+    pub const RESET: Self = Reg0bValues {
+        reg_reset: false,
+        part_number: 0b0010,
+        sgm_part: true,
+        device_revision: 0,
+    };
+
+    /// Returns the chip identification, or `None` if PN and SGMPART do not
+    /// match a known part.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sgm41511::types::*;
+    /// let info = Reg0bValues::try_from(0b00010101).unwrap().device_info();
+    /// assert_eq!(info, Some(DeviceInfo { part_number: PartNumber::SGM41511, device_revision: 1 }));
+    ///
+    /// let info = Reg0bValues::try_from(0b00001000).unwrap().device_info();
+    /// assert_eq!(info, None);
+    /// ```
+    pub fn device_info(&self) -> Option<DeviceInfo> {
+        if !self.sgm_part {
+            return None;
+        }
+        Some(DeviceInfo {
+            part_number: PartNumber::try_from(self.part_number).ok()?,
+            device_revision: self.device_revision,
+        })
+    }
+}
+
+/// Converts `u8` to `Reg0bValues`
+///
+/// # Examples
+///
+/// ```rust
+/// use sgm41511::types::*;
+/// let values = Reg0bValues::try_from(0b00010110).unwrap();
+/// assert_eq!(values, Reg0bValues {
+///     reg_reset: false,
+///     part_number: 0b0010,
+///     sgm_part: true,
+///     device_revision: 0b10,
+/// });
+///
+/// let values = Reg0bValues::try_from(0b10000000).unwrap();
+/// assert_eq!(values, Reg0bValues {
+///     reg_reset: true,
+///     part_number: 0b0000,
+///     sgm_part: false,
+///     device_revision: 0b00,
+/// });
+/// ```
+impl TryFrom<u8> for Reg0bValues {
+    type Error = InvalidValue;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(Reg0bValues::decode_lenient(value))
+    }
+}
+
+impl DecodeLenient for Reg0bValues {
+    fn decode_lenient(value: u8) -> Self {
+        Reg0bValues {
+            reg_reset: value & 0x80 != 0,
+            part_number: (value & 0x78) >> 3,
+            sgm_part: value & 0x04 != 0,
+            device_revision: value & 0x03,
+        }
+    }
+}
+
+/// Converts `Reg0bValues` to `u8`
+///
+/// # Examples
+///
+/// ```rust
+/// use sgm41511::types::*;
+/// let values: u8 = Reg0bValues {
+///     reg_reset: false,
+///     part_number: 0b0010,
+///     sgm_part: true,
+///     device_revision: 0b10,
+/// }.into();
+/// assert_eq!(values, 0b00010110);
+///
+/// let values: u8 = Reg0bValues {
+///     reg_reset: true,
+///     part_number: 0b0000,
+///     sgm_part: false,
+///     device_revision: 0b00,
+/// }.into();
+/// assert_eq!(values, 0b10000000);
+/// ```
+impl From<Reg0bValues> for u8 {
+    fn from(values: Reg0bValues) -> Self {
+        let mut value = 0u8;
+        if values.reg_reset {
+            value |= 0x80;
+        }
+        value |= (values.part_number & 0x0f) << 3;
+        if values.sgm_part {
+            value |= 0x04;
+        }
+        value |= values.device_revision & 0x03;
+        value
+    }
+}
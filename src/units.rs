@@ -0,0 +1,274 @@
+//! Conversions between the field enums and physical units.
+//!
+//! Every current setting has `as_milliamps` / `from_milliamps` and every
+//! voltage setting `as_millivolts` / `from_millivolts`. The constructors take
+//! a [`Rounding`] mode, since most requested values fall between two steps.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sgm41511::types::*;
+//! use sgm41511::units::{ConversionError, Rounding};
+//! assert_eq!(
+//!     ChargeCurrent::from_milliamps(2000, Rounding::Floor),
+//!     Ok(ChargeCurrent::_1980mA)
+//! );
+//! assert_eq!(
+//!     ChargeCurrent::from_milliamps(2000, Rounding::Ceil),
+//!     Ok(ChargeCurrent::_2040mA)
+//! );
+//! assert_eq!(
+//!     ChargeCurrent::from_milliamps(2000, Rounding::Exact),
+//!     Err(ConversionError::Inexact)
+//! );
+//! assert_eq!(ChargeVoltageLimit::_4_208V.as_millivolts(), 4208);
+//! ```
+
+use crate::types::*;
+
+/// How a physical value between two register steps is mapped to a setting.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Rounding {
+    /// Largest setting not above the requested value.
+    Floor,
+    /// Smallest setting not below the requested value.
+    Ceil,
+    /// Closest setting, clamped to the range. Ties go to the lower setting.
+    Nearest,
+    /// The requested value must be a setting.
+    Exact,
+}
+
+/// A physical value that has no setting under the requested [`Rounding`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConversionError {
+    /// Below the lowest setting (`Floor` or `Exact`).
+    BelowRange,
+    /// Above the highest setting (`Ceil` or `Exact`).
+    AboveRange,
+    /// Inside the range but not a setting (`Exact`).
+    Inexact,
+}
+
+/// Picks the setting for `target` among the codes `0..=max_code`.
+///
+/// The settings do not need to be sorted, MIN_BAT_SEL for example is not.
+fn select<T>(
+    target: u16,
+    rounding: Rounding,
+    max_code: u8,
+    unit: fn(T) -> u16,
+) -> Result<T, ConversionError>
+where
+    T: TryFrom<u8, Error = InvalidValue> + Copy,
+{
+    let mut floor: Option<(T, u16)> = None;
+    let mut ceil: Option<(T, u16)> = None;
+    for code in 0..=max_code {
+        let Ok(setting) = T::try_from(code) else {
+            continue;
+        };
+        let value = unit(setting);
+        if value <= target && floor.map_or(true, |(_, best)| value > best) {
+            floor = Some((setting, value));
+        }
+        if value >= target && ceil.map_or(true, |(_, best)| value < best) {
+            ceil = Some((setting, value));
+        }
+    }
+    match (rounding, floor, ceil) {
+        (Rounding::Floor, Some((setting, _)), _) => Ok(setting),
+        (Rounding::Floor, None, _) => Err(ConversionError::BelowRange),
+        (Rounding::Ceil, _, Some((setting, _))) => Ok(setting),
+        (Rounding::Ceil, _, None) => Err(ConversionError::AboveRange),
+        (Rounding::Nearest, Some((low, low_value)), Some((high, high_value))) => {
+            if high_value - target < target - low_value {
+                Ok(high)
+            } else {
+                Ok(low)
+            }
+        }
+        (Rounding::Nearest, Some((setting, _)), None)
+        | (Rounding::Nearest, None, Some((setting, _))) => Ok(setting),
+        // Only if no code decodes, every field has at least one setting.
+        (Rounding::Nearest, None, None) => Err(ConversionError::AboveRange),
+        (Rounding::Exact, Some((setting, value)), _) if value == target => Ok(setting),
+        (Rounding::Exact, None, _) => Err(ConversionError::BelowRange),
+        (Rounding::Exact, _, None) => Err(ConversionError::AboveRange),
+        (Rounding::Exact, _, _) => Err(ConversionError::Inexact),
+    }
+}
+
+impl InputCurrentLimit {
+    /// Input current limit in mA, 100 - 3200 mA in 100 mA steps.
+    pub const fn as_milliamps(self) -> u16 {
+        100 + self as u16 * 100
+    }
+
+    pub fn from_milliamps(milliamps: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(milliamps, rounding, 0x1f, Self::as_milliamps)
+    }
+}
+
+impl MinSystemVoltage {
+    /// Minimum system voltage in mV.
+    pub const fn as_millivolts(self) -> u16 {
+        match self {
+            MinSystemVoltage::_2_6V => 2600,
+            MinSystemVoltage::_2_8V => 2800,
+            MinSystemVoltage::_3_0V => 3000,
+            MinSystemVoltage::_3_2V => 3200,
+            MinSystemVoltage::_3_4V => 3400,
+            MinSystemVoltage::_3_5V => 3500,
+            MinSystemVoltage::_3_6V => 3600,
+            MinSystemVoltage::_3_7V => 3700,
+        }
+    }
+
+    pub fn from_millivolts(millivolts: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(millivolts, rounding, 0x07, Self::as_millivolts)
+    }
+}
+
+impl MinBatteryVoltageForOtG {
+    /// Minimum battery voltage for OTG (boost) mode in mV.
+    pub const fn as_millivolts(self) -> u16 {
+        match self {
+            MinBatteryVoltageForOtG::_2_95V => 2950,
+            MinBatteryVoltageForOtG::_2_6V => 2600,
+        }
+    }
+
+    pub fn from_millivolts(millivolts: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(millivolts, rounding, 0x01, Self::as_millivolts)
+    }
+}
+
+impl BoostCurrentLimit {
+    /// Boost mode current limit in mA.
+    pub const fn as_milliamps(self) -> u16 {
+        match self {
+            BoostCurrentLimit::_0_5A => 500,
+            BoostCurrentLimit::_1_25A => 1250,
+        }
+    }
+
+    pub fn from_milliamps(milliamps: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(milliamps, rounding, 0x01, Self::as_milliamps)
+    }
+}
+
+impl ChargeCurrent {
+    /// Fast charge current in mA, 0 - 3000 mA in 60 mA steps.
+    pub const fn as_milliamps(self) -> u16 {
+        self as u16 * 60
+    }
+
+    pub fn from_milliamps(milliamps: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(milliamps, rounding, 0x32, Self::as_milliamps)
+    }
+}
+
+impl PreChargeCurrent {
+    /// Pre-charge current in mA, 60 - 780 mA in 60 mA steps.
+    pub const fn as_milliamps(self) -> u16 {
+        60 + self as u16 * 60
+    }
+
+    pub fn from_milliamps(milliamps: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(milliamps, rounding, 0x0c, Self::as_milliamps)
+    }
+}
+
+impl TermChargeCurrent {
+    /// Termination current in mA, 60 - 900 mA in 60 mA steps.
+    pub const fn as_milliamps(self) -> u16 {
+        60 + self as u16 * 60
+    }
+
+    pub fn from_milliamps(milliamps: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(milliamps, rounding, 0x0e, Self::as_milliamps)
+    }
+}
+
+impl ChargeVoltageLimit {
+    /// Charge voltage limit in mV, 3856 - 4624 mV in 32 mV steps, except
+    /// for the special 4352 mV setting at 0x0f.
+    pub const fn as_millivolts(self) -> u16 {
+        match self {
+            ChargeVoltageLimit::_4_352V => 4352,
+            _ => 3856 + self as u16 * 32,
+        }
+    }
+
+    pub fn from_millivolts(millivolts: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(millivolts, rounding, 0x18, Self::as_millivolts)
+    }
+}
+
+impl BatteryRechargeThreshold {
+    /// Recharge threshold offset below VREG in mV.
+    pub const fn as_millivolts(self) -> u16 {
+        match self {
+            BatteryRechargeThreshold::_100mV => 100,
+            BatteryRechargeThreshold::_200mV => 200,
+        }
+    }
+
+    pub fn from_millivolts(millivolts: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(millivolts, rounding, 0x01, Self::as_millivolts)
+    }
+}
+
+impl OVPThreshold {
+    /// VAC over-voltage protection threshold in mV.
+    pub const fn as_millivolts(self) -> u16 {
+        match self {
+            OVPThreshold::_5_5V => 5500,
+            OVPThreshold::_6_5V => 6500,
+            OVPThreshold::_10_5V => 10500,
+            OVPThreshold::_14V => 14000,
+        }
+    }
+
+    pub fn from_millivolts(millivolts: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(millivolts, rounding, 0x03, Self::as_millivolts)
+    }
+}
+
+impl BoostModeVoltage {
+    /// Boost mode output voltage in mV, 4850 - 5300 mV in 150 mV steps.
+    pub const fn as_millivolts(self) -> u16 {
+        4850 + self as u16 * 150
+    }
+
+    pub fn from_millivolts(millivolts: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(millivolts, rounding, 0x03, Self::as_millivolts)
+    }
+}
+
+impl VINDPMThreshold {
+    /// Input voltage regulation threshold in mV, 3900 - 5400 mV in 100 mV
+    /// steps.
+    pub const fn as_millivolts(self) -> u16 {
+        3900 + self as u16 * 100
+    }
+
+    pub fn from_millivolts(millivolts: u16, rounding: Rounding) -> Result<Self, ConversionError> {
+        select(millivolts, rounding, 0x0f, Self::as_millivolts)
+    }
+}
+
+impl WatchDogTimerSetting {
+    /// Watchdog timer period in seconds, `None` if the watchdog is disabled.
+    pub const fn as_seconds(self) -> Option<u16> {
+        match self {
+            WatchDogTimerSetting::Disabled => None,
+            WatchDogTimerSetting::_40Seconds => Some(40),
+            WatchDogTimerSetting::_80Seconds => Some(80),
+            WatchDogTimerSetting::_160Seconds => Some(160),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Cross-field checks of a [`ChargerConfig`].
+//!
+//! Every field of a configuration can hold a valid setting on its own while
+//! the combination makes no sense, for example a termination current above
+//! the fast charge current. [`ChargerConfig::validate`] reports such
+//! combinations as [`Issue`]s, split into hard errors and advisory warnings.
+//! No allocation is needed, the report is a bit set.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use sgm41511::config::ChargerConfig;
+//! use sgm41511::types::*;
+//! use sgm41511::validate::{Issue, Severity};
+//! let config = ChargerConfig {
+//!     charge_current: ChargeCurrent::_120mA,
+//!     term_charge_current: TermChargeCurrent::_180mA,
+//!     ..ChargerConfig::default()
+//! };
+//! let report = config.validate();
+//! assert!(report.has_errors());
+//! assert!(report.contains(Issue::TermAboveChargeCurrent));
+//! assert_eq!(Issue::TermAboveChargeCurrent.code(), 101);
+//! assert_eq!(Issue::TermAboveChargeCurrent.severity(), Severity::Error);
+//! ```
+
+use core::fmt;
+
+use crate::config::{ChargerConfig, ConfigField};
+
+/// Smallest gap between VINDPM and the OVP threshold, in mV, that does not
+/// raise [`Issue::VindpmNearOvp`].
+pub const VINDPM_OVP_MARGIN_MV: u16 = 500;
+
+/// Smallest gap between SYS_MIN and VREG, in mV, that does not raise
+/// [`Issue::SysMinNearVreg`].
+pub const SYS_MIN_VREG_MARGIN_MV: u16 = 200;
+
+/// How serious an [`Issue`] is.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Severity {
+    /// The configuration must not be applied.
+    Error,
+    /// The configuration works but is probably not what was intended.
+    Warning,
+}
+
+/// A problem found by [`ChargerConfig::validate`].
+///
+/// The numeric [`code`](Issue::code) of each issue is stable across
+/// releases: 1xx are errors, 2xx are warnings.
+#[repr(u8)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Issue {
+    /// ITERM is above a non-zero ICHG, so charging terminates immediately.
+    TermAboveChargeCurrent,
+    /// VINDPM is less than [`VINDPM_OVP_MARGIN_MV`] below the OVP threshold.
+    /// An adapter that overshoots on a load step trips OVP and cuts the input
+    /// instead of being held at VINDPM.
+    VindpmNearOvp,
+    /// SYS_MIN is less than [`SYS_MIN_VREG_MARGIN_MV`] below VREG. While the
+    /// battery is below SYS_MIN the system rail is regulated about 150 mV
+    /// above it, which leaves next to no room below the charge voltage.
+    SysMinNearVreg,
+    /// Termination and the safety timer are both disabled, so charging never
+    /// stops by itself.
+    NoChargeTermination,
+    /// IPRECHG is above a non-zero ICHG.
+    PreChargeAboveChargeCurrent,
+    /// Charging and OTG are both enabled. The chip only runs one of them.
+    ChargeAndOtgEnabled,
+    /// Charging is enabled with the BATFET turned off.
+    BatfetDisabledWhileCharging,
+}
+
+impl Issue {
+    /// Every issue, errors first.
+    pub const ALL: [Issue; 7] = [
+        Issue::TermAboveChargeCurrent,
+        Issue::VindpmNearOvp,
+        Issue::SysMinNearVreg,
+        Issue::NoChargeTermination,
+        Issue::PreChargeAboveChargeCurrent,
+        Issue::ChargeAndOtgEnabled,
+        Issue::BatfetDisabledWhileCharging,
+    ];
+
+    /// Stable numeric code of the issue.
+    pub const fn code(self) -> u16 {
+        match self {
+            Issue::TermAboveChargeCurrent => 101,
+            Issue::VindpmNearOvp => 102,
+            Issue::SysMinNearVreg => 103,
+            Issue::NoChargeTermination => 201,
+            Issue::PreChargeAboveChargeCurrent => 202,
+            Issue::ChargeAndOtgEnabled => 203,
+            Issue::BatfetDisabledWhileCharging => 204,
+        }
+    }
+
+    pub const fn severity(self) -> Severity {
+        match self {
+            Issue::TermAboveChargeCurrent | Issue::VindpmNearOvp | Issue::SysMinNearVreg => {
+                Severity::Error
+            }
+            Issue::NoChargeTermination
+            | Issue::PreChargeAboveChargeCurrent
+            | Issue::ChargeAndOtgEnabled
+            | Issue::BatfetDisabledWhileCharging => Severity::Warning,
+        }
+    }
+
+    /// The configuration fields involved in the issue.
+    pub const fn fields(self) -> &'static [ConfigField] {
+        match self {
+            Issue::TermAboveChargeCurrent => {
+                &[ConfigField::TermChargeCurrent, ConfigField::ChargeCurrent]
+            }
+            Issue::VindpmNearOvp => &[ConfigField::VindpmThreshold, ConfigField::OvpThreshold],
+            Issue::SysMinNearVreg => &[ConfigField::SysMinVoltage, ConfigField::ChargeVoltageLimit],
+            Issue::NoChargeTermination => &[ConfigField::TermEnabled, ConfigField::TimerEnabled],
+            Issue::PreChargeAboveChargeCurrent => {
+                &[ConfigField::PreChargeCurrent, ConfigField::ChargeCurrent]
+            }
+            Issue::ChargeAndOtgEnabled => &[ConfigField::ChargeEnabled, ConfigField::OtgEnabled],
+            Issue::BatfetDisabledWhileCharging => {
+                &[ConfigField::BatfetDisabled, ConfigField::ChargeEnabled]
+            }
+        }
+    }
+
+    pub const fn message(self) -> &'static str {
+        match self {
+            Issue::TermAboveChargeCurrent => "termination current above fast charge current",
+            Issue::VindpmNearOvp => "VINDPM threshold too close to OVP threshold",
+            Issue::SysMinNearVreg => "minimum system voltage too close to charge voltage limit",
+            Issue::NoChargeTermination => "termination and safety timer both disabled",
+            Issue::PreChargeAboveChargeCurrent => "pre-charge current above fast charge current",
+            Issue::ChargeAndOtgEnabled => "charge and OTG both enabled",
+            Issue::BatfetDisabledWhileCharging => "charge enabled with BATFET disabled",
+        }
+    }
+}
+
+/// Formats the issue as `E101: message` or `W201: message`.
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.severity() {
+            Severity::Error => 'E',
+            Severity::Warning => 'W',
+        };
+        write!(f, "{}{}: {}", prefix, self.code(), self.message())
+    }
+}
+
+/// The issues found in a configuration.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ValidationReport(u8);
+
+impl ValidationReport {
+    /// Returns `true` if nothing was found, not even a warning.
+    pub const fn is_clean(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn has_warnings(&self) -> bool {
+        self.warnings().next().is_some()
+    }
+
+    pub const fn contains(&self, issue: Issue) -> bool {
+        self.0 & (1 << issue as u8) != 0
+    }
+
+    fn insert(&mut self, issue: Issue) {
+        self.0 |= 1 << issue as u8;
+    }
+
+    /// Iterates over every issue found, errors first.
+    pub fn iter(&self) -> impl Iterator<Item = Issue> + '_ {
+        Issue::ALL.into_iter().filter(|issue| self.contains(*issue))
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = Issue> + '_ {
+        self.iter()
+            .filter(|issue| issue.severity() == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = Issue> + '_ {
+        self.iter()
+            .filter(|issue| issue.severity() == Severity::Warning)
+    }
+}
+
+impl ChargerConfig {
+    /// Checks the configuration for combinations of settings that conflict.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let charge_current = self.charge_current.as_milliamps();
+
+        if charge_current != 0 && self.term_charge_current.as_milliamps() > charge_current {
+            report.insert(Issue::TermAboveChargeCurrent);
+        }
+        if self.vindpm_threshold.as_millivolts() + VINDPM_OVP_MARGIN_MV
+            > self.ovp_threshold.as_millivolts()
+        {
+            report.insert(Issue::VindpmNearOvp);
+        }
+        if self.sys_min_voltage.as_millivolts() + SYS_MIN_VREG_MARGIN_MV
+            > self.charge_voltage_limit.as_millivolts()
+        {
+            report.insert(Issue::SysMinNearVreg);
+        }
+        if !self.term_enabled && !self.timer_enabled {
+            report.insert(Issue::NoChargeTermination);
+        }
+        if charge_current != 0 && self.pre_charge_current.as_milliamps() > charge_current {
+            report.insert(Issue::PreChargeAboveChargeCurrent);
+        }
+        if self.charge_enabled && self.otg_enabled {
+            report.insert(Issue::ChargeAndOtgEnabled);
+        }
+        if self.charge_enabled && self.batfet_disabled {
+            report.insert(Issue::BatfetDisabledWhileCharging);
+        }
+        report
+    }
+}
@@ -0,0 +1,187 @@
+//! Servicing of the I2C watchdog.
+//!
+//! While the watchdog timer in REG05 is enabled, the chip expects the host
+//! to set WD_RST before the timer expires. If it does not, the chip leaves
+//! host mode and its charge settings fall back to their defaults.
+//! [`WatchdogService`] kicks the watchdog at half the configured period and,
+//! through a [`ResetMonitor`], applies the configuration again whenever the
+//! chip was reset anyway. While the watchdog is disabled, it only checks for
+//! resets, once per second.
+//!
+//! A watchdog expiry is only noticed, and counted as a missed kick, when it
+//! reverted some settings of the applied configuration. Telling an expiry
+//! apart otherwise takes a read of WATCHDOG_FAULT in REG09, which would clear
+//! every other latched fault as well, so the service does not do it. A
+//! configuration left entirely at its power-on values has nothing to lose,
+//! but its expiries go uncounted.
+
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+#[cfg(feature = "async")]
+use core::convert::Infallible;
+
+use crate::config::ChargerConfig;
+#[cfg(feature = "async")]
+use crate::driver::SGM41511Async;
+use crate::driver::SGM41511;
+use crate::recovery::{ChipResetDetected, ResetCause, ResetMonitor};
+use crate::Error;
+
+/// Interval of the reset checks while the watchdog is disabled.
+const DISABLED_CHECK_INTERVAL_MS: u32 = 1000;
+
+/// Keeps the chip in host mode by kicking the I2C watchdog.
+///
+/// Superloops call [`tick`](WatchdogService::tick) as often as they like
+/// with the current time in milliseconds; the service only touches the bus
+/// when a kick is due. With the `async` feature, `run` services the
+/// watchdog from a task instead.
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WatchdogService {
+    monitor: ResetMonitor,
+    last_service: Option<u64>,
+    missed_kicks: u32,
+}
+
+impl WatchdogService {
+    /// Services a chip that `config` was already applied to, see
+    /// [`ResetMonitor::new`].
+    pub const fn new(config: ChargerConfig) -> Self {
+        WatchdogService {
+            monitor: ResetMonitor::new(config),
+            last_service: None,
+            missed_kicks: 0,
+        }
+    }
+
+    pub const fn monitor(&self) -> &ResetMonitor {
+        &self.monitor
+    }
+
+    pub fn monitor_mut(&mut self) -> &mut ResetMonitor {
+        &mut self.monitor
+    }
+
+    /// Time between two kicks in milliseconds: half the watchdog period of
+    /// the stored configuration. `None` if the watchdog is disabled.
+    pub fn kick_interval_ms(&self) -> Option<u32> {
+        let period = self.monitor.config().watchdog_timer_setting.as_seconds()?;
+        Some(u32::from(period) * 1000 / 2)
+    }
+
+    /// Time between two services in milliseconds: the kick interval, or one
+    /// second while the watchdog is disabled and the chip is only checked
+    /// for resets.
+    pub fn service_interval_ms(&self) -> u32 {
+        self.kick_interval_ms()
+            .unwrap_or(DISABLED_CHECK_INTERVAL_MS)
+    }
+
+    /// Number of resets found that the chip put down to a watchdog expiry.
+    /// Expiries that reverted no setting are not counted, see the module
+    /// documentation.
+    pub const fn missed_kicks(&self) -> u32 {
+        self.missed_kicks
+    }
+
+    /// Returns `true` if a service is due at `now_ms`.
+    pub fn is_due(&self, now_ms: u64) -> bool {
+        match self.last_service {
+            None => true,
+            Some(last) => now_ms.saturating_sub(last) >= u64::from(self.service_interval_ms()),
+        }
+    }
+
+    fn record(&mut self, detected: &Option<ChipResetDetected>) {
+        if let Some(ChipResetDetected {
+            cause: ResetCause::WatchdogExpired,
+            ..
+        }) = detected
+        {
+            self.missed_kicks = self.missed_kicks.saturating_add(1);
+        }
+    }
+}
+
+#[maybe_async_cfg::maybe(
+    idents(
+        I2c(sync = "BlockingI2c", async = "AsyncI2c"),
+        SGM41511(sync = "SGM41511", async = "SGM41511Async"),
+        check(sync = "check", async = "check_async"),
+        service(sync = "service", async = "service_async"),
+        tick(sync = "tick", async = "tick_async")
+    ),
+    sync(keep_self),
+    async(feature = "async", keep_self)
+)]
+impl WatchdogService {
+    /// Services the chip if a service is due at `now_ms`.
+    ///
+    /// The settings are checked for a reset, which is repaired and returned,
+    /// and then the watchdog is kicked unless it is disabled in the stored
+    /// configuration. REG09 is only read when a reset was found, so latched
+    /// faults are left for [`read_faults`](SGM41511::read_faults) and the
+    /// event engine.
+    pub async fn tick<I2C, E>(
+        &mut self,
+        device: &mut SGM41511<I2C>,
+        now_ms: u64,
+    ) -> Result<Option<ChipResetDetected>, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        if !self.is_due(now_ms) {
+            return Ok(None);
+        }
+        let detected = Self::service(self, device).await?;
+        self.last_service = Some(now_ms);
+        Ok(detected)
+    }
+
+    async fn service<I2C, E>(
+        &mut self,
+        device: &mut SGM41511<I2C>,
+    ) -> Result<Option<ChipResetDetected>, Error<E>>
+    where
+        I2C: I2c<Error = E>,
+    {
+        let detected = ResetMonitor::check(&mut self.monitor, device).await?;
+        self.record(&detected);
+        if self.kick_interval_ms().is_some() {
+            device.kick_watchdog().await?;
+        }
+        Ok(detected)
+    }
+}
+
+#[cfg(feature = "async")]
+impl WatchdogService {
+    /// Services the chip forever, every
+    /// [`service_interval_ms`](Self::service_interval_ms) like
+    /// [`tick`](Self::tick) does, calling `on_reset` for every reset found.
+    /// Only returns on a bus or decoding error.
+    pub async fn run<I2C, E, D, F>(
+        &mut self,
+        device: &mut SGM41511Async<I2C>,
+        mut delay: D,
+        mut on_reset: F,
+    ) -> Result<Infallible, Error<E>>
+    where
+        I2C: AsyncI2c<Error = E>,
+        D: DelayNs,
+        F: FnMut(ChipResetDetected),
+    {
+        loop {
+            if let Some(detected) = self.service_async(device).await? {
+                on_reset(detected);
+            }
+            delay.delay_ms(self.service_interval_ms()).await;
+        }
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use embedded_hal::i2c::ErrorKind;
     use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
     use sgm41511::*;
 
@@ -20,4 +21,292 @@ mod tests {
 
         assert_eq!(revision, Some(0));
     }
+
+    #[test]
+    fn test_bus_error_is_wrapped() {
+        let expectations =
+            [
+                Transaction::write_read(SGM41511_ADDR, vec![Register::Reg08 as u8], vec![0x00])
+                    .with_error(ErrorKind::Other),
+            ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let result = device.get_reg08();
+
+        i2c.done();
+
+        assert_eq!(result, Err(Error::Bus(ErrorKind::Other)));
+    }
+
+    #[test]
+    fn test_invalid_field_value_is_reported() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg09 as u8], vec![0b00000001]),
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg09 as u8], vec![0b00000001]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let strict = device.get_reg09();
+        let lenient = device.get_lenient::<types::Reg09Values>(Register::Reg09);
+
+        i2c.done();
+
+        assert_eq!(
+            strict,
+            Err(Error::InvalidFieldValue {
+                register: Register::Reg09,
+                raw: 0b00000001
+            })
+        );
+        let lenient = lenient.unwrap();
+        assert_eq!(lenient.value.ntc_fault, types::NtcFault::Normal);
+        assert_eq!(lenient.raw, 0b00000001);
+        assert!(!lenient.is_exact());
+    }
+
+    #[test]
+    fn test_identify() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg0b as u8], vec![0b00010101]),
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg0b as u8], vec![0b00101000]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let info = device.identify();
+        let unexpected = device.identify();
+
+        i2c.done();
+
+        assert_eq!(
+            info,
+            Ok(types::DeviceInfo {
+                part_number: types::PartNumber::SGM41511,
+                device_revision: 1,
+            })
+        );
+        assert_eq!(unexpected, Err(Error::UnexpectedDevice { raw: 0b00101000 }));
+    }
+
+    #[test]
+    fn test_set_interrupt_mask_preserves_other_bits() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg0a as u8], vec![0b10010001]),
+            Transaction::write(SGM41511_ADDR, vec![Register::Reg0a as u8, 0b10010010]),
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg0a as u8], vec![0b10010010]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        device
+            .set_interrupt_mask(types::InterruptMask::VINDPM)
+            .unwrap();
+        let mask = device.get_interrupt_mask().unwrap();
+
+        i2c.done();
+
+        assert_eq!(mask, types::InterruptMask::VINDPM);
+    }
+
+    #[test]
+    fn test_modify_writes_only_on_change() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg01 as u8], vec![0b00011010]),
+            Transaction::write(SGM41511_ADDR, vec![Register::Reg01 as u8, 0b00001010]),
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg01 as u8], vec![0b00001010]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let disabled = device
+            .modify_reg01(|values| values.charge_enabled = false)
+            .unwrap();
+        let unchanged = device
+            .modify_reg01(|values| values.charge_enabled = false)
+            .unwrap();
+
+        i2c.done();
+
+        assert!(disabled.old.charge_enabled);
+        assert!(!disabled.new.charge_enabled);
+        assert!(disabled.changed());
+        assert!(!unchanged.changed());
+    }
+
+    #[test]
+    fn test_read_all_is_a_single_burst() {
+        let raw = vec![
+            0x17, 0x1a, 0xa2, 0x22, 0x58, 0x9f, 0x66, 0x4c, 0x54, 0x00, 0x80, 0x15,
+        ];
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg00 as u8], raw.clone()),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg08 as u8],
+                raw[8..11].to_vec(),
+            ),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let map = device.read_all().unwrap();
+        let status = device.read_status().unwrap();
+
+        i2c.done();
+
+        assert_eq!(map.as_raw().as_slice(), raw.as_slice());
+        assert_eq!(
+            map.reg00().unwrap().input_milliamps_limit,
+            types::InputCurrentLimit::_2400mA
+        );
+        assert_eq!(map.status(), status);
+        assert_eq!(
+            status.reg08().unwrap().charge_status,
+            types::ChargeStatus::Fast
+        );
+        assert!(status.reg0a().unwrap().vbus_gd);
+    }
+
+    #[test]
+    fn test_generic_read_and_write() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg06 as u8], vec![0b01100110]),
+            Transaction::write(SGM41511_ADDR, vec![Register::Reg06 as u8, 0b10100110]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let mut values = device.read::<types::Reg06Values>().unwrap();
+        values.ovp_threshold = types::OVPThreshold::_10_5V;
+        device.write::<types::Reg06Values>(values).unwrap();
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_set_charge_current_ma_reports_programmed_value() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg02 as u8], vec![0xa2]),
+            Transaction::write(SGM41511_ADDR, vec![Register::Reg02 as u8, 0xa1]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let programmed = device.set_charge_current_ma(2000).unwrap();
+        let rejected = device.set_precharge_current_ma(30);
+
+        i2c.done();
+
+        assert_eq!(programmed, 1980);
+        assert_eq!(
+            rejected,
+            Err(Error::Conversion(units::ConversionError::BelowRange))
+        );
+    }
+
+    #[test]
+    fn test_reset_constants_match_register_map() {
+        use sgm41511::register_map::RegisterMap;
+        use sgm41511::types::*;
+
+        let encoded: [u8; 12] = [
+            Reg00Values::RESET.into(),
+            Reg01Values::RESET.into(),
+            Reg02Values::RESET.into(),
+            Reg03Values::RESET.into(),
+            Reg04Values::RESET.into(),
+            Reg05Values::RESET.into(),
+            Reg06Values::RESET.into(),
+            Reg07Values::RESET.into(),
+            Reg08Values::RESET.into(),
+            Reg09Values::RESET.into(),
+            Reg0aValues::RESET.into(),
+            Reg0bValues::RESET.into(),
+        ];
+
+        assert_eq!(&encoded, RegisterMap::RESET.as_raw());
+        assert!(RegisterMap::RESET.diff_from_reset().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_faults_reads_reg09_twice() {
+        use sgm41511::faults::Fault;
+
+        let expectations = [
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg09 as u8],
+                vec![0b1010_0000],
+            ),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg09 as u8],
+                vec![0b0000_0000],
+            ),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let report = device.read_faults().unwrap();
+
+        i2c.done();
+
+        assert!(report.latched.watchdog_fault);
+        assert!(report.active_faults().is_empty());
+        assert_eq!(
+            report.cleared_faults().iter().collect::<Vec<_>>(),
+            [Fault::Watchdog, Fault::ThermalShutdown]
+        );
+    }
+
+    #[test]
+    fn test_status_derives_charge_state() {
+        use sgm41511::status::*;
+
+        let expectations = [
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg08 as u8],
+                vec![0x7c, 0x00, 0x88],
+            ),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg08 as u8],
+                vec![0x60, 0x00, 0x80],
+            ),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg08 as u8],
+                vec![0x00, 0x00, 0x00],
+            ),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let top_off = device.status().unwrap();
+        let not_good = device.status().unwrap();
+        let unplugged = device.status().unwrap();
+
+        i2c.done();
+
+        assert_eq!(top_off.state, ChargeState::TopOff);
+        assert!(top_off.reg0a.topoff_active);
+        assert_eq!(
+            not_good.state,
+            ChargeState::Suspended(SuspendReason::PowerNotGood)
+        );
+        assert_eq!(unplugged.state, ChargeState::NotConnected);
+    }
 }
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+    use sgm41511::blocking::CachedSGM41511;
+    use sgm41511::types::*;
+    use sgm41511::*;
+
+    #[test]
+    fn test_modifications_are_flushed_once() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg02 as u8], vec![0b10100010]),
+            Transaction::write(SGM41511_ADDR, vec![Register::Reg02 as u8, 0b11000000]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = CachedSGM41511::new(SGM41511::new(i2c.clone()));
+        device
+            .modify::<Reg02Values, _>(|values| values.charge_current = ChargeCurrent::_0mA)
+            .unwrap();
+        device
+            .modify::<Reg02Values, _>(|values| values.q1_full_on = Q1FullOnMode::Efficiency)
+            .unwrap();
+        assert!(device.is_dirty(Register::Reg02));
+        device.flush().unwrap();
+        assert!(!device.is_dirty(Register::Reg02));
+        device.flush().unwrap();
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_watchdog_reset_bypasses_cache() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg01 as u8], vec![0b01011010]),
+            Transaction::write(SGM41511_ADDR, vec![Register::Reg01 as u8, 0b01011010]),
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg08 as u8], vec![0x00]),
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg01 as u8], vec![0b00011010]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = CachedSGM41511::new(SGM41511::new(i2c.clone()));
+        let values = device.read::<Reg01Values>().unwrap();
+        assert!(!values.watchdog_reset);
+        device.kick_watchdog().unwrap();
+        device.read::<Reg08Values>().unwrap();
+        device.invalidate();
+        device.read::<Reg01Values>().unwrap();
+
+        i2c.done();
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+    use sgm41511::config::*;
+    use sgm41511::register_map::RegisterMap;
+    use sgm41511::types::*;
+    use sgm41511::*;
+
+    const RESET: [u8; 12] = *RegisterMap::RESET.as_raw();
+
+    /// The transactions of `read_settings` on a chip holding `raw`.
+    fn read_settings(raw: [u8; 12]) -> Vec<Transaction> {
+        vec![
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg00 as u8],
+                raw[..=Register::Reg08 as usize].to_vec(),
+            ),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg0a as u8],
+                vec![raw[Register::Reg0a as usize]],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_apply_writes_only_differing_registers() {
+        let config = ChargerConfig {
+            charge_current: ChargeCurrent::_1020mA,
+            ..ChargerConfig::default()
+        };
+        let mut expectations = read_settings(RESET);
+        expectations.push(Transaction::write(
+            SGM41511_ADDR,
+            vec![Register::Reg02 as u8, 0x91],
+        ));
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        config.apply(&mut device).unwrap();
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_apply_overwrites_undefined_codes() {
+        let config = ChargerConfig::default();
+        let mut raw = RESET;
+        // VREG code 0x1f is reserved
+        raw[Register::Reg04 as usize] = 0xf8;
+        let mut expectations = read_settings(raw);
+        expectations.push(Transaction::write(
+            SGM41511_ADDR,
+            vec![Register::Reg04 as u8, RESET[4]],
+        ));
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        config.apply(&mut device).unwrap();
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_apply_disables_before_changing_limits() {
+        // Charging goes off and OTG on, HIZ mode is entered.
+        let config = ChargerConfig {
+            charge_enabled: false,
+            otg_enabled: true,
+            en_hiz: true,
+            charge_current: ChargeCurrent::_1020mA,
+            ..ChargerConfig::default()
+        };
+        let write = |register: Register, value: u8| {
+            Transaction::write(SGM41511_ADDR, vec![register as u8, value])
+        };
+        let mut expectations = read_settings(RESET);
+        expectations.extend([
+            write(Register::Reg01, 0x0a),
+            write(Register::Reg00, 0x97),
+            write(Register::Reg02, 0x91),
+            write(Register::Reg01, 0x2a),
+        ]);
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        config.apply(&mut device).unwrap();
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_verify_reports_mismatching_fields() {
+        let config = ChargerConfig {
+            charge_current: ChargeCurrent::_1020mA,
+            ..ChargerConfig::default()
+        };
+        let expectations = read_settings(RESET);
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let readback = config.verify(&mut device).unwrap();
+
+        i2c.done();
+
+        assert_eq!(readback.actual, ChargerConfig::default());
+        let mismatches = readback.mismatches;
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches.contains(ConfigField::ChargeCurrent));
+        assert_eq!(
+            mismatches.iter().next().map(ConfigField::register),
+            Some(Register::Reg02)
+        );
+    }
+
+    #[test]
+    fn test_verify_reports_undefined_codes() {
+        let mut raw = RESET;
+        raw[Register::Reg04 as usize] = 0xf8;
+        let expectations = read_settings(raw);
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let clamped = Reg04Values::decode_lenient(0xf8).charge_voltage_limit;
+        let config = ChargerConfig {
+            charge_voltage_limit: clamped,
+            ..ChargerConfig::default()
+        };
+        let readback = config.verify(&mut device).unwrap();
+
+        i2c.done();
+
+        assert_eq!(readback.actual.charge_voltage_limit, clamped);
+        assert_eq!(
+            readback.mismatches.iter().collect::<Vec<_>>(),
+            [ConfigField::ChargeVoltageLimit]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+    use sgm41511::events::*;
+    use sgm41511::faults::Fault;
+    use sgm41511::register_map::StatusRegisters;
+    use sgm41511::types::*;
+    use sgm41511::*;
+
+    fn snapshot(raw: [u8; 3]) -> StatusSnapshot {
+        StatusSnapshot::from(StatusRegisters::from_raw(raw))
+    }
+
+    fn drain<const N: usize>(engine: &mut EventEngine<N>) -> Vec<Event> {
+        core::iter::from_fn(|| engine.pop()).collect()
+    }
+
+    #[test]
+    fn test_transitions_are_reported_once() {
+        let mut engine = EventEngine::<16>::new();
+        engine.update(snapshot([0x74, 0x00, 0x80]));
+        drain(&mut engine);
+
+        // Charge done, NTC hot, VINDPM active, top-off running.
+        engine.update(snapshot([0x7c, 0x06, 0xc8]));
+        assert_eq!(
+            drain(&mut engine),
+            [
+                Event::ChargePhaseChanged {
+                    from: ChargeStatus::Fast,
+                    to: ChargeStatus::Terminated
+                },
+                Event::FaultRaised(Fault::NtcHot),
+                Event::DpmEntered(Dpm::Voltage),
+                Event::TopOffStarted,
+            ]
+        );
+
+        engine.update(snapshot([0x7c, 0x06, 0xc8]));
+        assert_eq!(drain(&mut engine), []);
+
+        engine.update(snapshot([0x00, 0x00, 0x00]));
+        assert_eq!(
+            drain(&mut engine),
+            [
+                Event::InputDetached,
+                Event::ChargePhaseChanged {
+                    from: ChargeStatus::Terminated,
+                    to: ChargeStatus::Disabled
+                },
+                Event::FaultCleared(Fault::NtcHot),
+                Event::DpmExited(Dpm::Voltage),
+                Event::TopOffEnded,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_full_queue_drops_oldest() {
+        let mut queue = EventQueue::<2>::new();
+        queue.push(Event::TopOffStarted);
+        queue.push(Event::TopOffEnded);
+        queue.push(Event::InputDetached);
+
+        assert_eq!(queue.dropped(), 1);
+        assert_eq!(queue.pop(), Some(Event::TopOffEnded));
+        assert_eq!(queue.pop(), Some(Event::InputDetached));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn test_poll_reads_status_in_one_burst() {
+        let expectations = [Transaction::write_read(
+            SGM41511_ADDR,
+            vec![Register::Reg08 as u8],
+            vec![0x24, 0x00, 0x80],
+        )];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let mut engine = EventEngine::<4>::new();
+        let queued = engine.poll(&mut device).unwrap();
+
+        i2c.done();
+
+        assert_eq!(queued, 1);
+        assert_eq!(
+            engine.pop(),
+            Some(Event::InputAttached {
+                source: VBUSStatus::USBHostSDP
+            })
+        );
+    }
+}
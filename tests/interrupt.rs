@@ -0,0 +1,78 @@
+#![cfg(feature = "async")]
+
+#[cfg(test)]
+mod tests {
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    use embedded_hal_mock::eh1::digital::{Edge, Mock as PinMock, Transaction as PinTransaction};
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+    use sgm41511::events::{Dpm, Event};
+    use sgm41511::faults::Fault;
+    use sgm41511::interrupt::InterruptPin;
+    use sgm41511::*;
+
+    /// The mocks complete immediately, so a single poll is enough.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut context = Context::from_waker(Waker::noop());
+        match pin!(future).poll(&mut context) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("mock future did not complete"),
+        }
+    }
+
+    #[test]
+    fn test_wait_for_event_skips_masked_dpm() {
+        let expectations = [
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg08 as u8],
+                vec![0x74, 0x00, 0xe2],
+            ),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg08 as u8],
+                vec![0x74, 0x08, 0x82],
+            ),
+        ];
+        let pin_expectations = [
+            PinTransaction::wait_for_edge(Edge::Falling),
+            PinTransaction::wait_for_edge(Edge::Falling),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+        let mut int = PinMock::new(&pin_expectations);
+
+        let mut device = asynch::SGM41511::new(i2c.clone());
+        let mut pin = InterruptPin::<_, 8>::new(int.clone());
+
+        // VINDPM is masked, IINDPM is not.
+        let mut first = block_on(pin.wait_for_event(&mut device)).unwrap();
+        assert_eq!(
+            core::iter::from_fn(|| first.reasons.pop()).collect::<Vec<_>>(),
+            [
+                Event::InputAttached {
+                    source: types::VBUSStatus::USBDCP
+                },
+                Event::ChargePhaseChanged {
+                    from: types::ChargeStatus::Disabled,
+                    to: types::ChargeStatus::Fast
+                },
+                Event::DpmEntered(Dpm::Current),
+            ]
+        );
+
+        let mut second = block_on(pin.wait_for_event(&mut device)).unwrap();
+        assert_eq!(
+            core::iter::from_fn(|| second.reasons.pop()).collect::<Vec<_>>(),
+            [
+                Event::FaultRaised(Fault::Battery),
+                Event::DpmExited(Dpm::Current),
+            ]
+        );
+
+        i2c.done();
+        int.done();
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+    use sgm41511::config::*;
+    use sgm41511::faults::Fault;
+    use sgm41511::recovery::*;
+    use sgm41511::register_map::RegisterMap;
+    use sgm41511::types::*;
+    use sgm41511::*;
+
+    fn config() -> ChargerConfig {
+        ChargerConfig {
+            charge_current: ChargeCurrent::_1020mA,
+            ..ChargerConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_watchdog_reset_is_detected_and_restored() {
+        let raw = *RegisterMap::RESET.as_raw();
+        let expectations = [
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg00 as u8],
+                raw[..=Register::Reg08 as usize].to_vec(),
+            ),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg0a as u8],
+                vec![raw[Register::Reg0a as usize]],
+            ),
+            Transaction::write_read(SGM41511_ADDR, vec![Register::Reg09 as u8], vec![0x88]),
+            Transaction::write(SGM41511_ADDR, vec![Register::Reg02 as u8, 0x91]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let mut monitor = ResetMonitor::new(config());
+        let event = monitor.check(&mut device).unwrap().unwrap();
+
+        i2c.done();
+
+        assert_eq!(event.cause, ResetCause::WatchdogExpired);
+        assert_eq!(
+            event.reverted.iter().collect::<Vec<_>>(),
+            [ConfigField::ChargeCurrent]
+        );
+        assert_eq!(
+            event.faults.iter().collect::<Vec<_>>(),
+            [Fault::Watchdog, Fault::Battery]
+        );
+    }
+
+    #[test]
+    fn test_check_leaves_faults_latched_without_reset() {
+        let mut raw = *RegisterMap::RESET.as_raw();
+        raw[Register::Reg02 as usize] = 0x91;
+        let expectations = [
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg00 as u8],
+                raw[..=Register::Reg08 as usize].to_vec(),
+            ),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg0a as u8],
+                vec![raw[Register::Reg0a as usize]],
+            ),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let mut monitor = ResetMonitor::new(config());
+        assert_eq!(monitor.check(&mut device).unwrap(), None);
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_new_config_waits_for_apply() {
+        let reset = *RegisterMap::RESET.as_raw();
+        let mut configured = reset;
+        configured[Register::Reg02 as usize] = 0x91;
+        let read_settings = |raw: [u8; 12]| {
+            [
+                Transaction::write_read(
+                    SGM41511_ADDR,
+                    vec![Register::Reg00 as u8],
+                    raw[..=Register::Reg08 as usize].to_vec(),
+                ),
+                Transaction::write_read(
+                    SGM41511_ADDR,
+                    vec![Register::Reg0a as u8],
+                    vec![raw[Register::Reg0a as usize]],
+                ),
+            ]
+        };
+        let mut expectations = Vec::new();
+        expectations.extend_from_slice(&read_settings(reset));
+        expectations.extend_from_slice(&read_settings(reset));
+        expectations.push(Transaction::write(
+            SGM41511_ADDR,
+            vec![Register::Reg02 as u8, 0x91],
+        ));
+        expectations.extend_from_slice(&read_settings(configured));
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let mut monitor = ResetMonitor::new(ChargerConfig::default());
+        monitor.set_config(config());
+        assert_eq!(monitor.check(&mut device).unwrap(), None);
+        assert_eq!(monitor.applied_config(), &ChargerConfig::default());
+
+        monitor.apply(&mut device).unwrap();
+        assert_eq!(monitor.applied_config(), &config());
+        assert_eq!(monitor.check(&mut device).unwrap(), None);
+
+        i2c.done();
+    }
+
+    #[test]
+    fn test_power_on_reset_is_told_apart_from_reconfiguration() {
+        let monitor = ResetMonitor::new(config());
+
+        let event = monitor.detect(&RegisterMap::RESET).unwrap().unwrap();
+        assert_eq!(event.cause, ResetCause::PowerOnReset);
+
+        let mut raw = *RegisterMap::RESET.as_raw();
+        raw[Register::Reg02 as usize] = 0x91;
+        assert_eq!(monitor.detect(&RegisterMap::from_raw(raw)), Ok(None));
+
+        raw[Register::Reg02 as usize] = 0x81;
+        assert_eq!(monitor.detect(&RegisterMap::from_raw(raw)), Ok(None));
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use embedded_hal::delay::DelayNs;
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+    use sgm41511::retry::*;
+    use sgm41511::*;
+
+    /// Records the delays instead of waiting.
+    #[derive(Default)]
+    struct RecordingDelay(Vec<u32>);
+
+    impl DelayNs for RecordingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.0.push(ns / 1000);
+        }
+    }
+
+    #[test]
+    fn test_retries_until_attempts_are_used_up() {
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data);
+        let expectations = [
+            Transaction::write(SGM41511_ADDR, vec![0x02, 0x91]).with_error(nack),
+            Transaction::write(SGM41511_ADDR, vec![0x02, 0x91]).with_error(ErrorKind::Bus),
+            Transaction::write(SGM41511_ADDR, vec![0x02, 0x91]),
+            Transaction::write(SGM41511_ADDR, vec![0x02, 0x91]).with_error(nack),
+            Transaction::write(SGM41511_ADDR, vec![0x02, 0x91]).with_error(nack),
+            Transaction::write(SGM41511_ADDR, vec![0x02, 0x91]).with_error(nack),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut bus = RetryI2c::new(i2c.clone(), RecordingDelay::default(), RetryPolicy::DEFAULT);
+        let mut device = SGM41511::new(&mut bus);
+        device.write_register(Register::Reg02, 0x91).unwrap();
+        assert!(matches!(
+            device.write_register(Register::Reg02, 0x91),
+            Err(Error::Bus(ErrorKind::NoAcknowledge(_)))
+        ));
+
+        i2c.done();
+
+        let (_, delay) = bus.release();
+        assert_eq!(delay.0, [100, 200, 100, 200]);
+    }
+
+    #[test]
+    fn test_counts_and_skips_non_retryable_errors() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![0x0b], vec![0x00])
+                .with_error(ErrorKind::ArbitrationLoss),
+            Transaction::write_read(SGM41511_ADDR, vec![0x0b], vec![0x14]),
+            Transaction::write_read(SGM41511_ADDR, vec![0x0b], vec![0x00])
+                .with_error(ErrorKind::Other),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut bus = RetryI2c::new(i2c.clone(), RecordingDelay::default(), RetryPolicy::DEFAULT);
+        let mut device = SGM41511::new(&mut bus);
+        assert_eq!(device.read_register(Register::Reg0b).unwrap(), 0x14);
+        assert!(device.read_register(Register::Reg0b).is_err());
+
+        i2c.done();
+
+        assert_eq!(
+            bus.stats(),
+            RetryStats {
+                transactions: 2,
+                retries: 1,
+                recovered: 1,
+                failed: 1,
+                fault_reads_not_retried: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fault_reads_are_not_retried() {
+        let expectations = [
+            Transaction::write_read(SGM41511_ADDR, vec![0x00], vec![0x00; 12])
+                .with_error(ErrorKind::Bus),
+            Transaction::write_read(SGM41511_ADDR, vec![0x09], vec![0x00])
+                .with_error(ErrorKind::Bus),
+            Transaction::write_read(SGM41511_ADDR, vec![0x0a], vec![0x00])
+                .with_error(ErrorKind::Bus),
+            Transaction::write_read(SGM41511_ADDR, vec![0x0a], vec![0x80]),
+        ];
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut bus = RetryI2c::new(i2c.clone(), RecordingDelay::default(), RetryPolicy::DEFAULT);
+        let mut device = SGM41511::new(&mut bus);
+        assert!(device.read_all().is_err());
+        assert!(device.read_register(Register::Reg09).is_err());
+        assert_eq!(device.read_register(Register::Reg0a).unwrap(), 0x80);
+
+        i2c.done();
+
+        assert_eq!(
+            bus.stats(),
+            RetryStats {
+                transactions: 3,
+                retries: 1,
+                recovered: 1,
+                failed: 2,
+                fault_reads_not_retried: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_total_backoff_is_limited() {
+        let expectations = [
+            Transaction::write(SGM41511_ADDR, vec![0x02, 0x91]).with_error(ErrorKind::Bus),
+            Transaction::write(SGM41511_ADDR, vec![0x02, 0x91]).with_error(ErrorKind::Bus),
+        ];
+        // The second retry would wait 2000 µs, 3000 µs in total.
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff_us: 1000,
+            max_total_backoff_us: 2500,
+            ..RetryPolicy::DEFAULT
+        };
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut bus = RetryI2c::new(i2c.clone(), RecordingDelay::default(), policy);
+        let mut device = SGM41511::new(&mut bus);
+        assert!(device.write_register(Register::Reg02, 0x91).is_err());
+
+        i2c.done();
+
+        let (_, delay) = bus.release();
+        assert_eq!(delay.0, [1000]);
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff_us: 1000,
+            backoff_multiplier: 4,
+            max_backoff_us: 20_000,
+            ..RetryPolicy::DEFAULT
+        };
+        let backoff: Vec<u32> = (1..=4).map(|retry| policy.backoff_us(retry)).collect();
+        assert_eq!(backoff, [1000, 4000, 16_000, 20_000]);
+        assert_eq!(RetryPolicy::NONE.backoff_us(1), 0);
+    }
+}
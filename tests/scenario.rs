@@ -0,0 +1,135 @@
+#![cfg(feature = "sim")]
+
+#[cfg(test)]
+mod tests {
+    use sgm41511::sim::*;
+    use sgm41511::SGM41511;
+
+    const WARM_UNPLUG: &str = r#"
+name = "5 V / 2 A DCP, warm at 30 min, unplugged at 2 h"
+capacity_mah = 2000
+soc = 0.2
+policy_interval = "10s"
+
+[[event]]
+at = 0
+action = "plug"
+adapter = "dcp"
+current_limit_ma = 2000
+
+[[event]]
+at = "30min"
+action = "temperature"
+celsius = 50
+
+[[event]]
+at = "2h"
+action = "unplug"
+
+[[expect]]
+at = "10min"
+field = "reg08.charge_status"
+equals = "Fast"
+
+[[expect]]
+at = "10min"
+field = "reg08.pg_status"
+equals = true
+
+[[expect]]
+at = "31min"
+field = "reg09.ntc_fault"
+equals = "Warm"
+
+[[expect]]
+at = "1h59min"
+field = "reg08.charge_status"
+equals = "Terminated"
+
+[[expect]]
+at = "2h"
+field = "reg08.vbus_status"
+equals = "NoInput"
+"#;
+
+    fn kick_watchdog(sim: &mut Simulation) {
+        SGM41511::new(sim).kick_watchdog().unwrap();
+    }
+
+    #[test]
+    fn test_scenario_passes() {
+        let scenario = Scenario::parse(WARM_UNPLUG).unwrap();
+        assert_eq!(scenario.actions.len(), 3);
+        assert_eq!(scenario.expectations[2].at_ms, 31 * 60 * 1000);
+
+        let mut policy_runs = 0;
+        let report = scenario
+            .run(|sim| {
+                policy_runs += 1;
+                kick_watchdog(sim);
+            })
+            .unwrap();
+        assert_eq!(report.checked, 5);
+        assert_eq!(report.duration_ms, 2 * 3600 * 1000);
+        assert_eq!(policy_runs, 721);
+    }
+
+    #[test]
+    fn test_failure_reports_time_and_line() {
+        let scenario = Scenario::parse(WARM_UNPLUG).unwrap();
+        // The policy keeps the battery cool, so the NTC never goes warm.
+        let mut sim = scenario.simulation();
+        let failure = scenario
+            .run_on(&mut sim, |sim| {
+                sim.set_temperature_c(20.0);
+            })
+            .unwrap_err();
+        assert_eq!(failure.at_ms, 31 * 60 * 1000);
+        assert_eq!(failure.line, 32);
+        assert_eq!(
+            failure.to_string(),
+            "0:31:00.000 (line 32): reg09.ntc_fault is Normal, expected Warm"
+        );
+    }
+
+    #[test]
+    fn test_policy_may_advance_time() {
+        let scenario = Scenario::parse(WARM_UNPLUG).unwrap();
+        // Every policy run takes longer than the policy interval.
+        let report = scenario
+            .run(|sim| {
+                kick_watchdog(sim);
+                sim.advance(15_000);
+            })
+            .unwrap();
+        assert_eq!(report.checked, 5);
+    }
+
+    #[test]
+    fn test_late_failure_reports_simulated_time() {
+        let scenario = Scenario::parse(
+            "policy_interval = \"10s\"\n[[expect]]\nat = \"5s\"\nfield = \"reg08.pg_status\"\nequals = true",
+        )
+        .unwrap();
+        let failure = scenario.run(|sim| sim.advance(7_000)).unwrap_err();
+        assert_eq!(failure.at_ms, 7_000);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Scenario::parse("soc = \"full\"").unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(error.message, "`soc` must be a number, not a string");
+
+        let error = Scenario::parse("\n[[expect]]\nat = \"1x\"").unwrap_err();
+        assert_eq!(error.to_string(), "line 3: invalid duration `1x`");
+
+        let error = Scenario::parse("[[expect]]\nat = 0\nfield = \"reg08.charging\"\nequals = 1")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "line 1: unknown field `reg08.charging`");
+
+        let error =
+            Scenario::parse("[[event]]\nat = 0\naction = \"unplug\"\ncelsius = 3").unwrap_err();
+        assert_eq!(error.to_string(), "line 4: unknown key `celsius`");
+    }
+}
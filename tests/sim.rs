@@ -0,0 +1,354 @@
+#![cfg(feature = "sim")]
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::i2c::{ErrorKind, I2c, NoAcknowledgeSource};
+    use sgm41511::config::*;
+    use sgm41511::faults::Fault;
+    use sgm41511::recovery::{ResetCause, ResetMonitor};
+    use sgm41511::sim::*;
+    use sgm41511::status::*;
+    use sgm41511::types::*;
+    use sgm41511::watchdog::WatchdogService;
+    use sgm41511::*;
+
+    #[test]
+    fn test_driver_runs_against_sim() {
+        let mut sim = Sgm41511Sim::new();
+        let mut device = SGM41511::new(&mut sim);
+
+        let id = device.identify().unwrap();
+        assert_eq!(id.part_number, PartNumber::SGM41511);
+
+        let map = device.read_all().unwrap();
+        assert_eq!(map.diff_from_reset().unwrap().len(), 0);
+
+        let config = ChargerConfig {
+            charge_current: ChargeCurrent::_1020mA,
+            charge_voltage_limit: ChargeVoltageLimit::_4_352V,
+            ..ChargerConfig::default()
+        };
+        config.apply(&mut device).unwrap();
+        assert!(config.verify(&mut device).unwrap().mismatches.is_empty());
+
+        device.kick_watchdog().unwrap();
+        assert!(!device.get_reg01().unwrap().watchdog_reset);
+        assert_eq!(sim.watchdog_kicks(), 1);
+    }
+
+    #[test]
+    fn test_read_only_bits_and_register_reset() {
+        let mut sim = Sgm41511Sim::new();
+        sim.set_conditions(0x80);
+        let mut device = SGM41511::new(&mut sim);
+
+        device.write_register(Register::Reg08, 0xff).unwrap();
+        device.write_register(Register::Reg0a, 0x03).unwrap();
+        device.write_register(Register::Reg0b, 0x0f).unwrap();
+        device.write_register(Register::Reg02, 0x91).unwrap();
+
+        assert_eq!(device.read_register(Register::Reg08).unwrap(), 0x00);
+        assert_eq!(device.read_register(Register::Reg0a).unwrap(), 0x83);
+        assert_eq!(device.read_register(Register::Reg0b).unwrap(), 0x14);
+
+        device.reset_register().unwrap();
+        assert_eq!(device.read_register(Register::Reg02).unwrap(), 0xa2);
+        assert_eq!(device.read_register(Register::Reg0a).unwrap(), 0x80);
+        assert_eq!(device.read_register(Register::Reg0b).unwrap(), 0x14);
+    }
+
+    #[test]
+    fn test_burst_and_address() {
+        let mut sim = Sgm41511Sim::new();
+        sim.write(SGM41511_ADDR, &[0x04, 0x5a, 0x9e]).unwrap();
+        let mut buffer = [0; 3];
+        sim.write_read(SGM41511_ADDR, &[0x03], &mut buffer).unwrap();
+        assert_eq!(buffer, [0x22, 0x5a, 0x9e]);
+
+        let mut next = [0];
+        sim.read(SGM41511_ADDR, &mut next).unwrap();
+        assert_eq!(next, [0x66]);
+
+        assert_eq!(
+            sim.write(0x6a, &[0x00]),
+            Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+        );
+    }
+
+    #[test]
+    fn test_latched_faults() {
+        let mut sim = Sgm41511Sim::new();
+        sim.raise_faults(0x08);
+        sim.clear_faults(0x08);
+        sim.expire_watchdog();
+        let mut device = SGM41511::new(&mut sim);
+
+        let report = device.read_faults().unwrap();
+        assert!(report.new_faults().contains(Fault::Watchdog));
+        assert!(report.new_faults().contains(Fault::Battery));
+        assert!(report.active_faults().is_empty());
+    }
+
+    fn simulation(soc: f32) -> Simulation {
+        let mut sim = Simulation::new(Battery::new(1000, soc));
+        SGM41511::new(&mut sim)
+            .modify_reg05(|reg05| reg05.watchdog_timer_setting = WatchDogTimerSetting::Disabled)
+            .unwrap();
+        sim
+    }
+
+    fn charge_status(sim: &Simulation) -> ChargeStatus {
+        sim.chip().registers().reg08().unwrap().charge_status
+    }
+
+    #[test]
+    fn test_full_charge_session() {
+        let mut sim = simulation(0.0);
+        sim.plug(Adapter::dcp(2000));
+        assert_eq!(
+            sim.chip()
+                .registers()
+                .reg00()
+                .unwrap()
+                .input_milliamps_limit,
+            InputCurrentLimit::_2400mA
+        );
+
+        let mut phases = vec![];
+        let done = sim.advance_until(6 * 3600 * 1000, |sim| {
+            let status = charge_status(sim);
+            if phases.last() != Some(&status) {
+                phases.push(status);
+            }
+            status == ChargeStatus::Terminated
+        });
+        assert!(done);
+        assert_eq!(
+            phases,
+            [
+                ChargeStatus::Pre,
+                ChargeStatus::Fast,
+                ChargeStatus::Terminated
+            ]
+        );
+        assert!(sim.battery().ocv_mv() > 4150.0);
+        assert!((30 * 60 * 1000..90 * 60 * 1000).contains(&sim.now_ms()));
+
+        // Recharge once the battery drops below VREG - VRECHG.
+        sim.unplug();
+        sim.set_system_load_ma(500);
+        sim.advance(30 * 60 * 1000);
+        sim.plug(Adapter::dcp(2000));
+        sim.advance(1000);
+        assert_eq!(charge_status(&sim), ChargeStatus::Fast);
+    }
+
+    #[test]
+    fn test_top_off_is_reported() {
+        let mut sim = simulation(0.9);
+        SGM41511::new(&mut sim)
+            .modify_reg04(|reg04| reg04.top_off_timer = TopOffTimer::_30Minutes)
+            .unwrap();
+        sim.plug(Adapter::dcp(2000));
+        sim.advance(1000);
+
+        let mut states = vec![];
+        for _ in 0..3 * 360 {
+            let state = SGM41511::new(&mut sim).status().unwrap().state;
+            if states.last() != Some(&state) {
+                states.push(state);
+            }
+            if state == ChargeState::Full {
+                break;
+            }
+            sim.advance(10_000);
+        }
+        assert_eq!(
+            states,
+            [
+                ChargeState::Charging(ChargePhase::Fast),
+                ChargeState::TopOff,
+                ChargeState::Full
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weak_adapter_enters_vindpm() {
+        let mut sim = simulation(0.5);
+        sim.plug(Adapter::dcp(500));
+        sim.advance(1000);
+
+        let conditions = sim.chip().registers().reg0a().unwrap();
+        assert!(conditions.vindpm_status);
+        assert!(!conditions.iindpm_status);
+        assert!(sim.input_current_ma() <= 500.0);
+        assert!(sim.battery_current_ma() > 0.0);
+        assert!(sim.battery_current_ma() < 1000.0);
+    }
+
+    #[test]
+    fn test_cold_battery_suspends_charging() {
+        let mut sim = simulation(0.5);
+        sim.plug(Adapter::dcp(2000));
+        sim.set_temperature_c(-5.0);
+        sim.advance(1000);
+
+        assert_eq!(charge_status(&sim), ChargeStatus::Disabled);
+        assert_eq!(sim.battery_current_ma(), 0.0);
+        let faults = SGM41511::new(&mut sim).read_faults().unwrap();
+        assert!(faults.active_faults().contains(Fault::NtcCold));
+    }
+
+    #[test]
+    fn test_safety_timer_expires() {
+        let mut sim = simulation(0.5);
+        SGM41511::new(&mut sim)
+            .modify_reg05(|reg05| reg05.term_enabled = false)
+            .unwrap();
+        sim.plug(Adapter::dcp(2000));
+        sim.set_step_ms(60 * 1000);
+        sim.advance(7 * 3600 * 1000);
+
+        assert_eq!(charge_status(&sim), ChargeStatus::Disabled);
+        let faults = SGM41511::new(&mut sim).read_faults().unwrap();
+        assert!(faults.active_faults().contains(Fault::SafetyTimer));
+    }
+
+    #[test]
+    fn test_watchdog_expiry_restores_defaults() {
+        let mut sim = Simulation::new(Battery::new(1000, 0.5));
+        SGM41511::new(&mut sim).set_charge_current_ma(300).unwrap();
+        sim.advance(30 * 1000);
+        SGM41511::new(&mut sim).kick_watchdog().unwrap();
+        sim.advance(30 * 1000);
+        assert_eq!(
+            sim.chip().registers().reg02().unwrap().charge_current,
+            ChargeCurrent::_300mA
+        );
+
+        sim.advance(15 * 1000);
+        let mut device = SGM41511::new(&mut sim);
+        assert_eq!(
+            device.get_reg02().unwrap().charge_current,
+            ChargeCurrent::_2040mA
+        );
+        assert!(device
+            .read_faults()
+            .unwrap()
+            .new_faults()
+            .contains(Fault::Watchdog));
+    }
+
+    fn corrupted_reads(seed: u32) -> Vec<u8> {
+        let mut injector = FaultInjector::new(seed);
+        injector.inject(Trigger::Random { per_mille: 500 }, Injection::Corrupt);
+        let mut sim = Sgm41511Sim::new();
+        sim.set_injector(injector);
+        let mut device = SGM41511::new(&mut sim);
+        (0..16)
+            .map(|_| device.read_register(Register::Reg02).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_injected_bus_errors() {
+        let mut injector = FaultInjector::new(7);
+        injector.inject(
+            Trigger::Transaction(3),
+            Injection::Fail(ErrorKind::ArbitrationLoss),
+        );
+        injector.stick_bits(Register::Reg0b, 0x03, 0x03);
+        let mut sim = Sgm41511Sim::new();
+        sim.set_injector(injector);
+        let mut device = SGM41511::new(&mut sim);
+
+        device.write_register(Register::Reg02, 0x91).unwrap();
+        assert_eq!(device.read_register(Register::Reg02).unwrap(), 0x91);
+        assert!(matches!(
+            device.write_register(Register::Reg02, 0x22),
+            Err(Error::Bus(ErrorKind::ArbitrationLoss))
+        ));
+        assert_eq!(device.read_register(Register::Reg02).unwrap(), 0x91);
+        assert_eq!(device.read_register(Register::Reg0b).unwrap(), 0x17);
+        assert_eq!(sim.injector().injected(), 1);
+    }
+
+    #[test]
+    fn test_corruption_reproduces_from_seed() {
+        let reads = corrupted_reads(42);
+        assert_eq!(reads, corrupted_reads(42));
+        assert_ne!(reads, corrupted_reads(43));
+        assert!(reads.contains(&0xa2));
+        assert!(reads.iter().any(|value| (value ^ 0xa2).count_ones() == 1));
+    }
+
+    #[test]
+    fn test_injected_reset_is_recovered() {
+        let config = ChargerConfig {
+            charge_current: ChargeCurrent::_1020mA,
+            ..ChargerConfig::default()
+        };
+        let mut sim = Sgm41511Sim::new();
+        let mut device = SGM41511::new(&mut sim);
+        config.apply(&mut device).unwrap();
+        let next = sim.transactions() + 1;
+        sim.injector_mut()
+            .inject(Trigger::Transaction(next), Injection::Reset);
+        sim.injector_mut()
+            .inject(Trigger::Transaction(next), Injection::LatchFaults(0x40));
+
+        let mut device = SGM41511::new(&mut sim);
+        let mut monitor = ResetMonitor::new(config);
+        let detected = monitor.check(&mut device).unwrap().unwrap();
+        assert_eq!(detected.cause, ResetCause::PowerOnReset);
+        assert!(config.verify(&mut device).unwrap().mismatches.is_empty());
+        assert!(device.read_faults().unwrap().new_faults().is_empty());
+    }
+
+    #[test]
+    fn test_watchdog_tick_keeps_faults_latched() {
+        let config = ChargerConfig {
+            charge_current: ChargeCurrent::_1020mA,
+            ..ChargerConfig::default()
+        };
+        let mut sim = Sgm41511Sim::new();
+        config.apply(&mut SGM41511::new(&mut sim)).unwrap();
+        let mut service = WatchdogService::new(config);
+        assert_eq!(service.tick(&mut SGM41511::new(&mut sim), 0).unwrap(), None);
+
+        sim.raise_faults(0x08);
+        sim.clear_faults(0x08);
+        assert_eq!(
+            service.tick(&mut SGM41511::new(&mut sim), 20_000).unwrap(),
+            None
+        );
+        assert_eq!(sim.watchdog_kicks(), 2);
+
+        let faults = SGM41511::new(&mut sim).read_faults().unwrap();
+        assert!(faults.latched.bat_fault);
+        assert!(!faults.current.bat_fault);
+    }
+
+    #[test]
+    fn test_injected_vbus_toggle() {
+        let mut sim = simulation(0.5);
+        sim.plug(Adapter::dcp(2000));
+        sim.advance(1000);
+        let next = sim.chip().transactions() + 1;
+        sim.chip_mut()
+            .injector_mut()
+            .inject(Trigger::Transaction(next), Injection::ToggleVbus);
+
+        let status = SGM41511::new(&mut sim).read_status().unwrap();
+        assert!(!status.reg08().unwrap().pg_status);
+        assert!(sim.adapter().is_none());
+        sim.advance(1000);
+        assert!(sim.battery_current_ma() <= 0.0);
+        assert_eq!(charge_status(&sim), ChargeStatus::Disabled);
+
+        sim.toggle_vbus();
+        sim.advance(1000);
+        assert_eq!(charge_status(&sim), ChargeStatus::Fast);
+    }
+}
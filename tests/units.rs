@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use sgm41511::types::*;
+    use sgm41511::units::*;
+
+    #[test]
+    fn test_exact_round_trip() {
+        for code in 0..=0x18 {
+            let setting = ChargeVoltageLimit::try_from(code).unwrap();
+            let millivolts = setting.as_millivolts();
+            assert_eq!(
+                ChargeVoltageLimit::from_millivolts(millivolts, Rounding::Exact),
+                Ok(setting)
+            );
+        }
+        assert_eq!(ChargeVoltageLimit::_4_352V.as_millivolts(), 4352);
+        assert_eq!(ChargeVoltageLimit::_4_368V.as_millivolts(), 4368);
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        assert_eq!(
+            VINDPMThreshold::from_millivolts(4550, Rounding::Nearest),
+            Ok(VINDPMThreshold::_4_5V)
+        );
+        assert_eq!(
+            VINDPMThreshold::from_millivolts(4551, Rounding::Nearest),
+            Ok(VINDPMThreshold::_4_6V)
+        );
+        assert_eq!(
+            VINDPMThreshold::from_millivolts(9000, Rounding::Nearest),
+            Ok(VINDPMThreshold::_5_4V)
+        );
+        assert_eq!(
+            VINDPMThreshold::from_millivolts(9000, Rounding::Floor),
+            Ok(VINDPMThreshold::_5_4V)
+        );
+        assert_eq!(
+            VINDPMThreshold::from_millivolts(9000, Rounding::Ceil),
+            Err(ConversionError::AboveRange)
+        );
+        assert_eq!(
+            InputCurrentLimit::from_milliamps(50, Rounding::Ceil),
+            Ok(InputCurrentLimit::_100mA)
+        );
+        assert_eq!(
+            InputCurrentLimit::from_milliamps(50, Rounding::Floor),
+            Err(ConversionError::BelowRange)
+        );
+        assert_eq!(
+            MinBatteryVoltageForOtG::from_millivolts(2800, Rounding::Floor),
+            Ok(MinBatteryVoltageForOtG::_2_6V)
+        );
+    }
+}
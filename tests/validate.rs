@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use sgm41511::config::ChargerConfig;
+    use sgm41511::types::*;
+    use sgm41511::validate::*;
+
+    #[test]
+    fn test_reset_configuration_is_clean() {
+        assert!(ChargerConfig::default().validate().is_clean());
+    }
+
+    #[test]
+    fn test_errors_and_warnings_are_split() {
+        let config = ChargerConfig {
+            charge_current: ChargeCurrent::_120mA,
+            term_charge_current: TermChargeCurrent::_180mA,
+            pre_charge_current: PreChargeCurrent::_240mA,
+            term_enabled: false,
+            timer_enabled: false,
+            ..ChargerConfig::default()
+        };
+        let report = config.validate();
+
+        assert_eq!(
+            report.errors().collect::<Vec<_>>(),
+            [Issue::TermAboveChargeCurrent]
+        );
+        assert_eq!(
+            report.warnings().collect::<Vec<_>>(),
+            [
+                Issue::NoChargeTermination,
+                Issue::PreChargeAboveChargeCurrent
+            ]
+        );
+        assert_eq!(
+            format!("{}", Issue::NoChargeTermination),
+            "W201: termination and safety timer both disabled"
+        );
+    }
+
+    #[test]
+    fn test_vindpm_near_ovp_is_reported() {
+        let config = ChargerConfig {
+            ovp_threshold: OVPThreshold::_5_5V,
+            vindpm_threshold: VINDPMThreshold::_5_1V,
+            ..ChargerConfig::default()
+        };
+        let report = config.validate();
+        assert_eq!(report.errors().collect::<Vec<_>>(), [Issue::VindpmNearOvp]);
+
+        let config = ChargerConfig {
+            vindpm_threshold: VINDPMThreshold::_5_0V,
+            ..config
+        };
+        assert!(config.validate().is_clean());
+    }
+
+    #[test]
+    fn test_sys_min_near_vreg_is_reported() {
+        let config = ChargerConfig {
+            sys_min_voltage: MinSystemVoltage::_3_7V,
+            charge_voltage_limit: ChargeVoltageLimit::_3_856V,
+            ..ChargerConfig::default()
+        };
+        let report = config.validate();
+        assert_eq!(report.errors().collect::<Vec<_>>(), [Issue::SysMinNearVreg]);
+
+        let config = ChargerConfig {
+            sys_min_voltage: MinSystemVoltage::_3_6V,
+            ..config
+        };
+        assert!(config.validate().is_clean());
+    }
+
+    #[test]
+    fn test_zero_charge_current_is_not_compared() {
+        let config = ChargerConfig {
+            charge_current: ChargeCurrent::_0mA,
+            ..ChargerConfig::default()
+        };
+        assert!(config.validate().is_clean());
+    }
+}
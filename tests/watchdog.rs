@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+    use sgm41511::config::{ChargerConfig, ConfigField};
+    use sgm41511::recovery::ResetCause;
+    use sgm41511::register_map::RegisterMap;
+    use sgm41511::types::*;
+    use sgm41511::watchdog::WatchdogService;
+    use sgm41511::*;
+
+    #[test]
+    fn test_tick_kicks_at_half_period() {
+        let config = ChargerConfig {
+            charge_current: ChargeCurrent::_1020mA,
+            ..ChargerConfig::default()
+        };
+        let reset = *RegisterMap::RESET.as_raw();
+        let mut configured = reset;
+        configured[Register::Reg02 as usize] = 0x91;
+        let read_settings = |raw: [u8; 12]| {
+            [
+                Transaction::write_read(
+                    SGM41511_ADDR,
+                    vec![Register::Reg00 as u8],
+                    raw[..=Register::Reg08 as usize].to_vec(),
+                ),
+                Transaction::write_read(
+                    SGM41511_ADDR,
+                    vec![Register::Reg0a as u8],
+                    vec![raw[Register::Reg0a as usize]],
+                ),
+            ]
+        };
+        let kick = [
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg01 as u8],
+                vec![reset[Register::Reg01 as usize]],
+            ),
+            Transaction::write(SGM41511_ADDR, vec![Register::Reg01 as u8, 0x5a]),
+        ];
+        let mut expectations = Vec::new();
+        expectations.extend_from_slice(&read_settings(configured));
+        expectations.extend_from_slice(&kick);
+        expectations.extend_from_slice(&read_settings(reset));
+        expectations.push(Transaction::write_read(
+            SGM41511_ADDR,
+            vec![Register::Reg09 as u8],
+            vec![0x80],
+        ));
+        expectations.push(Transaction::write(
+            SGM41511_ADDR,
+            vec![Register::Reg02 as u8, 0x91],
+        ));
+        expectations.extend_from_slice(&kick);
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let mut service = WatchdogService::new(config);
+        assert_eq!(service.kick_interval_ms(), Some(20_000));
+
+        assert_eq!(service.tick(&mut device, 0).unwrap(), None);
+        assert_eq!(service.tick(&mut device, 19_999).unwrap(), None);
+        let detected = service.tick(&mut device, 20_000).unwrap().unwrap();
+
+        i2c.done();
+
+        assert_eq!(detected.cause, ResetCause::WatchdogExpired);
+        assert_eq!(
+            detected.reverted.iter().collect::<Vec<_>>(),
+            [ConfigField::ChargeCurrent]
+        );
+        assert_eq!(service.missed_kicks(), 1);
+    }
+
+    #[test]
+    fn test_disabled_watchdog_is_only_checked() {
+        let mut raw = *RegisterMap::RESET.as_raw();
+        raw[Register::Reg05 as usize] = 0x8f;
+        let read_settings = [
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg00 as u8],
+                raw[..=Register::Reg08 as usize].to_vec(),
+            ),
+            Transaction::write_read(
+                SGM41511_ADDR,
+                vec![Register::Reg0a as u8],
+                vec![raw[Register::Reg0a as usize]],
+            ),
+        ];
+        let expectations = [read_settings.clone(), read_settings].concat();
+
+        let mut i2c = Mock::new(&expectations);
+
+        let mut device = SGM41511::new(i2c.clone());
+        let mut service = WatchdogService::new(ChargerConfig {
+            watchdog_timer_setting: WatchDogTimerSetting::Disabled,
+            ..ChargerConfig::default()
+        });
+
+        assert_eq!(service.kick_interval_ms(), None);
+        assert_eq!(service.service_interval_ms(), 1000);
+        assert_eq!(service.tick(&mut device, 0).unwrap(), None);
+        assert_eq!(service.tick(&mut device, 999).unwrap(), None);
+        assert_eq!(service.tick(&mut device, 1000).unwrap(), None);
+
+        i2c.done();
+    }
+}